@@ -0,0 +1,1279 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Host-side assembly of [`ProofDataRequest`]s.
+//!
+//! A [`ProofDataRequestBuilder`] fetches the inputs of a proof without L1 inclusion through a [`ChainProvider`]:
+//! the view call input, the linking blocks required by the reorg protection policy of the chain and, for chains
+//! validated through a sequencer, the sequencer commitment and the OpStack L1Block input. The assembled request is
+//! dry-run against the typed checks of the guest validators before it is serialized for the guest.
+//!
+//! [`RpcProvider`] fetches from RPC endpoints, [`FileProvider`] replays fixtures for offline tests, and
+//! [`RecordingProvider`] records the fixtures of an RPC run.
+
+use alloy::eips::{BlockId, BlockNumberOrTag};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy_consensus::Header;
+use alloy_primitives::{Address, Bytes, B256};
+use alloy_sol_types::SolCall;
+use malda_utils::{constants::*, types::*, validators::*};
+use risc0_steel::{
+    ethereum::{EthChainSpec, EthEvmEnv, EthEvmInput},
+    serde::RlpHeader,
+    Contract,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Errors returned when a proof data request can't be assembled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PreflightError {
+    /// No endpoint is configured for the chain.
+    UnknownChain(u64),
+    /// The provider failed to fetch data of the chain.
+    Provider { chain_id: u64, message: String },
+    /// The builder can't assemble requests for the chain.
+    Unsupported { chain_id: u64, reason: &'static str },
+    /// The chain has no block old enough to satisfy its reorg protection policy.
+    ChainTooShort { chain_id: u64 },
+    /// The reorg protection policy can't be satisfied within the maximum number of linking blocks.
+    TooManyLinkingBlocks { chain_id: u64, max: u64 },
+    /// A contract call failed while executing the request.
+    Call { chain_id: u64, message: String },
+    /// The request targets chains whose journal entries the destinations reject.
+    TargetChain(TargetChainError),
+    /// A sequencer commitment doesn't vouch for the validated block.
+    SequencerCommitment(SequencerCommitmentError),
+    /// The seals of a Linea block are invalid.
+    LineaSeal(LineaSealError),
+    /// The linking blocks fail the reorg protection checks.
+    LinkingBlocks(LinkingBlocksError),
+    /// The results of the proof data multicall are invalid.
+    ProofData(ProofDataError),
+    /// The entries require L1 inclusion.
+    L1InclusionPolicy(L1InclusionPolicyError),
+    /// The request or a fixture couldn't be (de)serialized.
+    Serialization(String),
+}
+
+impl core::fmt::Display for PreflightError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PreflightError::UnknownChain(chain_id) => {
+                write!(f, "no endpoint for chain {chain_id}")
+            }
+            PreflightError::Provider { chain_id, message } => {
+                write!(f, "provider error on chain {chain_id}: {message}")
+            }
+            PreflightError::Unsupported { chain_id, reason } => {
+                write!(f, "unsupported chain {chain_id}: {reason}")
+            }
+            PreflightError::ChainTooShort { chain_id } => {
+                write!(f, "chain {chain_id} too short for reorg protection")
+            }
+            PreflightError::TooManyLinkingBlocks { chain_id, max } => write!(
+                f,
+                "reorg protection of chain {chain_id} not satisfiable within {max} linking blocks"
+            ),
+            PreflightError::Call { chain_id, message } => {
+                write!(f, "call failed on chain {chain_id}: {message}")
+            }
+            PreflightError::TargetChain(err) => write!(f, "{err}"),
+            PreflightError::SequencerCommitment(err) => write!(f, "{err}"),
+            PreflightError::LineaSeal(err) => write!(f, "{err}"),
+            PreflightError::LinkingBlocks(err) => write!(f, "{err}"),
+            PreflightError::ProofData(err) => write!(f, "{err}"),
+            PreflightError::L1InclusionPolicy(err) => write!(f, "{err}"),
+            PreflightError::Serialization(message) => {
+                write!(f, "serialization failed: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PreflightError {}
+
+impl From<TargetChainError> for PreflightError {
+    fn from(err: TargetChainError) -> Self {
+        Self::TargetChain(err)
+    }
+}
+
+impl From<SequencerCommitmentError> for PreflightError {
+    fn from(err: SequencerCommitmentError) -> Self {
+        Self::SequencerCommitment(err)
+    }
+}
+
+impl From<LineaSealError> for PreflightError {
+    fn from(err: LineaSealError) -> Self {
+        Self::LineaSeal(err)
+    }
+}
+
+impl From<LinkingBlocksError> for PreflightError {
+    fn from(err: LinkingBlocksError) -> Self {
+        Self::LinkingBlocks(err)
+    }
+}
+
+impl From<ProofDataError> for PreflightError {
+    fn from(err: ProofDataError) -> Self {
+        Self::ProofData(err)
+    }
+}
+
+impl From<L1InclusionPolicyError> for PreflightError {
+    fn from(err: L1InclusionPolicyError) -> Self {
+        Self::L1InclusionPolicy(err)
+    }
+}
+
+/// Source of the chain data a proof data request is assembled from.
+pub trait ChainProvider {
+    /// Returns the number of the latest block of the chain.
+    fn latest_block_number(&self, chain_id: u64) -> Result<u64, PreflightError>;
+
+    /// Returns the header of the block with the given number.
+    fn header_by_number(
+        &self,
+        chain_id: u64,
+        block_number: u64,
+    ) -> Result<RlpHeader<Header>, PreflightError>;
+
+    /// Returns the header of the block with the given hash.
+    fn header_by_hash(
+        &self,
+        chain_id: u64,
+        block_hash: B256,
+    ) -> Result<RlpHeader<Header>, PreflightError>;
+
+    /// Returns the latest sequencer commitment of the OpStack chain.
+    fn sequencer_commitment(&self, chain_id: u64) -> Result<SequencerCommitment, PreflightError>;
+
+    /// Returns the input for executing the proof data multicall at the given block.
+    fn proof_data_input(
+        &self,
+        chain_id: u64,
+        chain_spec: &EthChainSpec,
+        block_number: u64,
+        multicall: &IMulticall3::aggregate3Call,
+    ) -> Result<EthEvmInput, PreflightError>;
+
+    /// Returns the input for reading the L1 block hash from the L1Block contract of the OpStack chain at the
+    /// given block.
+    fn l1_block_input(
+        &self,
+        chain_id: u64,
+        chain_spec: &EthChainSpec,
+        block_number: u64,
+    ) -> Result<EthEvmInput, PreflightError>;
+}
+
+/// Endpoints of one chain.
+#[derive(Clone, Debug)]
+pub struct ChainEndpoint {
+    /// JSON-RPC endpoint of the chain.
+    pub rpc_url: Url,
+    /// Endpoint serving the latest sequencer commitment as JSON, required for OpStack chains.
+    pub sequencer_commitment_url: Option<Url>,
+}
+
+/// Chain provider fetching from RPC endpoints.
+pub struct RpcProvider {
+    endpoints: BTreeMap<u64, ChainEndpoint>,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl RpcProvider {
+    /// Creates a provider for the chains of the given endpoints.
+    pub fn new(endpoints: BTreeMap<u64, ChainEndpoint>) -> std::io::Result<Self> {
+        let runtime = tokio::runtime::Runtime::new()?;
+        Ok(Self { endpoints, runtime })
+    }
+
+    fn endpoint(&self, chain_id: u64) -> Result<&ChainEndpoint, PreflightError> {
+        self.endpoints
+            .get(&chain_id)
+            .ok_or(PreflightError::UnknownChain(chain_id))
+    }
+
+    fn header(&self, chain_id: u64, block: BlockId) -> Result<RlpHeader<Header>, PreflightError> {
+        let provider =
+            ProviderBuilder::new().connect_http(self.endpoint(chain_id)?.rpc_url.clone());
+        let rpc_block = self
+            .runtime
+            .block_on(async { provider.get_block(block).await })
+            .map_err(|err| provider_error(chain_id, err))?
+            .ok_or_else(|| provider_error(chain_id, format!("block {block} not found")))?;
+        Ok(RlpHeader::new(rpc_block.header.inner))
+    }
+
+    fn preflight_env<C: SolCall>(
+        &self,
+        chain_id: u64,
+        chain_spec: &EthChainSpec,
+        block_number: u64,
+        address: Address,
+        call: &C,
+    ) -> Result<EthEvmInput, PreflightError> {
+        let rpc_url = self.endpoint(chain_id)?.rpc_url.clone();
+        self.runtime
+            .block_on(async {
+                let mut env = EthEvmEnv::builder()
+                    .rpc(rpc_url)
+                    .chain_spec(chain_spec)
+                    .block_number(block_number)
+                    .build()
+                    .await?;
+                Contract::preflight(address, &mut env)
+                    .call_builder(call)
+                    .call()
+                    .await?;
+                env.into_input().await
+            })
+            .map_err(|err| provider_error(chain_id, err))
+    }
+}
+
+impl ChainProvider for RpcProvider {
+    fn latest_block_number(&self, chain_id: u64) -> Result<u64, PreflightError> {
+        let provider =
+            ProviderBuilder::new().connect_http(self.endpoint(chain_id)?.rpc_url.clone());
+        self.runtime
+            .block_on(async { provider.get_block_number().await })
+            .map_err(|err| provider_error(chain_id, err))
+    }
+
+    fn header_by_number(
+        &self,
+        chain_id: u64,
+        block_number: u64,
+    ) -> Result<RlpHeader<Header>, PreflightError> {
+        self.header(chain_id, BlockNumberOrTag::Number(block_number).into())
+    }
+
+    fn header_by_hash(
+        &self,
+        chain_id: u64,
+        block_hash: B256,
+    ) -> Result<RlpHeader<Header>, PreflightError> {
+        self.header(chain_id, block_hash.into())
+    }
+
+    fn sequencer_commitment(&self, chain_id: u64) -> Result<SequencerCommitment, PreflightError> {
+        let url = self
+            .endpoint(chain_id)?
+            .sequencer_commitment_url
+            .clone()
+            .ok_or(PreflightError::Unsupported {
+                chain_id,
+                reason: "no sequencer commitment endpoint",
+            })?;
+        self.runtime
+            .block_on(async {
+                reqwest::get(url)
+                    .await?
+                    .error_for_status()?
+                    .json::<SequencerCommitment>()
+                    .await
+            })
+            .map_err(|err| provider_error(chain_id, err))
+    }
+
+    fn proof_data_input(
+        &self,
+        chain_id: u64,
+        chain_spec: &EthChainSpec,
+        block_number: u64,
+        multicall: &IMulticall3::aggregate3Call,
+    ) -> Result<EthEvmInput, PreflightError> {
+        self.preflight_env(chain_id, chain_spec, block_number, MULTICALL, multicall)
+    }
+
+    fn l1_block_input(
+        &self,
+        chain_id: u64,
+        chain_spec: &EthChainSpec,
+        block_number: u64,
+    ) -> Result<EthEvmInput, PreflightError> {
+        self.preflight_env(
+            chain_id,
+            chain_spec,
+            block_number,
+            L1_BLOCK_ADDRESS_OPSTACK,
+            &IL1Block::hashCall {},
+        )
+    }
+}
+
+fn provider_error(chain_id: u64, err: impl core::fmt::Display) -> PreflightError {
+    PreflightError::Provider {
+        chain_id,
+        message: err.to_string(),
+    }
+}
+
+fn call_error(chain_id: u64, err: impl core::fmt::Display) -> PreflightError {
+    PreflightError::Call {
+        chain_id,
+        message: err.to_string(),
+    }
+}
+
+/// Chain provider replaying fixtures from a directory.
+///
+/// Fixtures are stored per chain as `<root>/<chain_id>/<kind>/<key>.bin`, encoded like guest input.
+/// The view call inputs are keyed by block only, so a fixture only replays the multicall it was recorded for.
+#[derive(Clone, Debug)]
+pub struct FileProvider {
+    root: PathBuf,
+}
+
+impl FileProvider {
+    /// Creates a provider reading fixtures below `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, chain_id: u64, kind: &str, key: impl core::fmt::Display) -> PathBuf {
+        self.root
+            .join(chain_id.to_string())
+            .join(kind)
+            .join(format!("{key}.bin"))
+    }
+
+    fn load<T: serde::de::DeserializeOwned>(
+        &self,
+        chain_id: u64,
+        kind: &str,
+        key: impl core::fmt::Display,
+    ) -> Result<T, PreflightError> {
+        let path = self.path(chain_id, kind, key);
+        let bytes = std::fs::read(&path)
+            .map_err(|err| provider_error(chain_id, format!("{}: {err}", path.display())))?;
+        decode(&bytes)
+    }
+
+    /// Stores a fixture that is returned for the given kind and key.
+    pub fn store<T: Serialize>(
+        &self,
+        chain_id: u64,
+        kind: &str,
+        key: impl core::fmt::Display,
+        value: &T,
+    ) -> Result<(), PreflightError> {
+        let path = self.path(chain_id, kind, key);
+        write_file(&path, &encode(value)?)
+            .map_err(|err| provider_error(chain_id, format!("{}: {err}", path.display())))
+    }
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, bytes)
+}
+
+/// Fixture kind of the latest block number.
+pub const FIXTURE_LATEST_BLOCK_NUMBER: &str = "latest_block_number";
+/// Fixture kind of headers, keyed by block number and by block hash.
+pub const FIXTURE_HEADER: &str = "header";
+/// Fixture kind of the latest sequencer commitment.
+pub const FIXTURE_SEQUENCER_COMMITMENT: &str = "sequencer_commitment";
+/// Fixture kind of view call inputs, keyed by block number.
+pub const FIXTURE_PROOF_DATA_INPUT: &str = "proof_data_input";
+/// Fixture kind of L1Block call inputs, keyed by block number.
+pub const FIXTURE_L1_BLOCK_INPUT: &str = "l1_block_input";
+
+impl ChainProvider for FileProvider {
+    fn latest_block_number(&self, chain_id: u64) -> Result<u64, PreflightError> {
+        self.load(chain_id, FIXTURE_LATEST_BLOCK_NUMBER, "latest")
+    }
+
+    fn header_by_number(
+        &self,
+        chain_id: u64,
+        block_number: u64,
+    ) -> Result<RlpHeader<Header>, PreflightError> {
+        self.load(chain_id, FIXTURE_HEADER, block_number)
+    }
+
+    fn header_by_hash(
+        &self,
+        chain_id: u64,
+        block_hash: B256,
+    ) -> Result<RlpHeader<Header>, PreflightError> {
+        self.load(chain_id, FIXTURE_HEADER, block_hash)
+    }
+
+    fn sequencer_commitment(&self, chain_id: u64) -> Result<SequencerCommitment, PreflightError> {
+        self.load(chain_id, FIXTURE_SEQUENCER_COMMITMENT, "latest")
+    }
+
+    fn proof_data_input(
+        &self,
+        chain_id: u64,
+        _chain_spec: &EthChainSpec,
+        block_number: u64,
+        _multicall: &IMulticall3::aggregate3Call,
+    ) -> Result<EthEvmInput, PreflightError> {
+        self.load(chain_id, FIXTURE_PROOF_DATA_INPUT, block_number)
+    }
+
+    fn l1_block_input(
+        &self,
+        chain_id: u64,
+        _chain_spec: &EthChainSpec,
+        block_number: u64,
+    ) -> Result<EthEvmInput, PreflightError> {
+        self.load(chain_id, FIXTURE_L1_BLOCK_INPUT, block_number)
+    }
+}
+
+/// Chain provider storing everything fetched from another provider as [`FileProvider`] fixtures.
+pub struct RecordingProvider<P> {
+    inner: P,
+    fixtures: FileProvider,
+}
+
+impl<P: ChainProvider> RecordingProvider<P> {
+    /// Creates a provider recording the responses of `inner` below `root`.
+    pub fn new(inner: P, root: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            fixtures: FileProvider::new(root),
+        }
+    }
+}
+
+impl<P: ChainProvider> ChainProvider for RecordingProvider<P> {
+    fn latest_block_number(&self, chain_id: u64) -> Result<u64, PreflightError> {
+        let block_number = self.inner.latest_block_number(chain_id)?;
+        self.fixtures.store(
+            chain_id,
+            FIXTURE_LATEST_BLOCK_NUMBER,
+            "latest",
+            &block_number,
+        )?;
+        Ok(block_number)
+    }
+
+    fn header_by_number(
+        &self,
+        chain_id: u64,
+        block_number: u64,
+    ) -> Result<RlpHeader<Header>, PreflightError> {
+        let header = self.inner.header_by_number(chain_id, block_number)?;
+        self.fixtures
+            .store(chain_id, FIXTURE_HEADER, block_number, &header)?;
+        Ok(header)
+    }
+
+    fn header_by_hash(
+        &self,
+        chain_id: u64,
+        block_hash: B256,
+    ) -> Result<RlpHeader<Header>, PreflightError> {
+        let header = self.inner.header_by_hash(chain_id, block_hash)?;
+        self.fixtures
+            .store(chain_id, FIXTURE_HEADER, block_hash, &header)?;
+        Ok(header)
+    }
+
+    fn sequencer_commitment(&self, chain_id: u64) -> Result<SequencerCommitment, PreflightError> {
+        let commitment = self.inner.sequencer_commitment(chain_id)?;
+        self.fixtures.store(
+            chain_id,
+            FIXTURE_SEQUENCER_COMMITMENT,
+            "latest",
+            &commitment,
+        )?;
+        Ok(commitment)
+    }
+
+    fn proof_data_input(
+        &self,
+        chain_id: u64,
+        chain_spec: &EthChainSpec,
+        block_number: u64,
+        multicall: &IMulticall3::aggregate3Call,
+    ) -> Result<EthEvmInput, PreflightError> {
+        let input = self
+            .inner
+            .proof_data_input(chain_id, chain_spec, block_number, multicall)?;
+        self.fixtures
+            .store(chain_id, FIXTURE_PROOF_DATA_INPUT, block_number, &input)?;
+        Ok(input)
+    }
+
+    fn l1_block_input(
+        &self,
+        chain_id: u64,
+        chain_spec: &EthChainSpec,
+        block_number: u64,
+    ) -> Result<EthEvmInput, PreflightError> {
+        let input = self
+            .inner
+            .l1_block_input(chain_id, chain_spec, block_number)?;
+        self.fixtures
+            .store(chain_id, FIXTURE_L1_BLOCK_INPUT, block_number, &input)?;
+        Ok(input)
+    }
+}
+
+/// Encodes a value like guest input, as read with `env::read`.
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, PreflightError> {
+    let words = risc0_zkvm::serde::to_vec(value)
+        .map_err(|err| PreflightError::Serialization(err.to_string()))?;
+    Ok(words.iter().flat_map(|word| word.to_le_bytes()).collect())
+}
+
+/// Decodes a value encoded with [`encode`].
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, PreflightError> {
+    if bytes.len() % 4 != 0 {
+        return Err(PreflightError::Serialization(
+            "length not a multiple of 4".to_string(),
+        ));
+    }
+    let words: Vec<u32> = bytes
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect();
+    risc0_zkvm::serde::from_slice(&words)
+        .map_err(|err| PreflightError::Serialization(err.to_string()))
+}
+
+/// Serializes a request as guest input, to be passed with `ExecutorEnv::builder().write_slice`.
+pub fn serialize_request(request: &ProofDataRequest) -> Result<Vec<u8>, PreflightError> {
+    encode(request)
+}
+
+/// Deserializes a request serialized with [`serialize_request`].
+pub fn deserialize_request(bytes: &[u8]) -> Result<ProofDataRequest, PreflightError> {
+    decode(bytes)
+}
+
+/// Runs the typed checks of the guest validators natively on a request and returns its journal entries.
+///
+/// Covers the requests assembled by [`ProofDataRequestBuilder`]. Requests with L1 inclusion, a finality proof,
+/// an ancestry claim or an L1 signer environment are rejected, as their assumptions and L1 reads can only be
+/// verified in the guest.
+///
+/// # Arguments
+/// * `request` - The proof data request to validate.
+///
+/// # Returns
+/// * `Result<Vec<Bytes>, PreflightError>` - The journal entries the guest would commit.
+pub fn dry_run(request: &ProofDataRequest) -> Result<Vec<Bytes>, PreflightError> {
+    let chain_id = request.chain_id;
+    let unsupported = |reason| PreflightError::Unsupported { chain_id, reason };
+    if request.env_input_eth_for_l1_inclusion.is_some()
+        || request.ethereum_finality_proof.is_some()
+        || request.ancestry_claim.is_some()
+        || request.env_input_eth_for_l1_signer.is_some()
+    {
+        return Err(unsupported(
+            "only requests validated by the sequencer can be dry-run",
+        ));
+    }
+    let rollup_config = request.opstack_rollup_config.as_ref();
+    match rollup_config {
+        Some(rollup_config)
+            if rollup_config.l2_chain_id != chain_id || is_builtin_chain(chain_id) =>
+        {
+            return Err(unsupported(
+                "rollup config for a built-in or different chain",
+            ))
+        }
+        None if !is_builtin_chain(chain_id) => return Err(unsupported("unknown chain")),
+        _ => {}
+    }
+    check_target_chain_ids(
+        chain_id,
+        rollup_config,
+        &request.target_opstack_rollup_configs,
+        request.account.len(),
+        request.asset.len(),
+        &request.target_chain_ids,
+    )?;
+
+    // Validate the block the linking blocks end at.
+    let chain_spec = match rollup_config {
+        Some(rollup_config) => get_eth_chain_spec(rollup_config.l1_chain_id),
+        None => get_eth_chain_spec(chain_id),
+    };
+    let env = request
+        .env_input_for_viewcall
+        .clone()
+        .ok_or(unsupported("missing view call input"))?
+        .into_env(chain_spec);
+    let head = match request.linking_blocks.last() {
+        Some(head) => head,
+        None => env.header().inner(),
+    };
+    let (validated_hash, sequencer_timestamp) = validate_head(request, head)?;
+
+    // Link the view call block to the validated block.
+    let policy = match rollup_config {
+        Some(rollup_config) => rollup_config.reorg_protection_policy(),
+        None => get_reorg_protection_policy(chain_id),
+    };
+    let historical_header = env.header().inner().inner();
+    let historical_hash = match chain_id {
+        LINEA_CHAIN_ID | LINEA_SEPOLIA_CHAIN_ID => get_block_hash(chain_id, historical_header),
+        _ => env.header().seal(),
+    };
+    check_linking_blocks(
+        chain_id,
+        &policy,
+        historical_hash,
+        historical_header,
+        &request.linking_blocks,
+        validated_hash,
+    )?;
+
+    // Execute the proof data multicall at the view call block.
+    let market_registry = if rollup_config.is_some() {
+        None
+    } else {
+        get_market_registry(chain_id)
+    };
+    let multicall = proof_data_multicall(
+        &request.account,
+        &request.asset,
+        &request.target_chain_ids,
+        market_registry,
+    );
+    let returns = Contract::new(MULTICALL, &env)
+        .call_builder(&multicall)
+        .try_call()
+        .map_err(|err| call_error(chain_id, err))?;
+    let amounts = decode_proof_data_returns(&request.asset, &returns)?;
+
+    // Configured chains are extension chains, which only target the host chain.
+    let rules: &[L1InclusionRule] = if rollup_config.is_some() {
+        &[]
+    } else {
+        get_l1_inclusion_rules(chain_id)
+    };
+    check_l1_inclusion_policy(
+        rules,
+        false,
+        &request.asset,
+        &request.target_chain_ids,
+        &amounts,
+    )?;
+
+    let chain_config_hash = rollup_config.map_or(B256::ZERO, OpStackRollupConfig::config_hash);
+    let batch_params = request
+        .account
+        .iter()
+        .zip(request.asset.iter())
+        .zip(request.target_chain_ids.iter());
+    Ok(batch_params
+        .zip(amounts)
+        .map(|(((user, market), target_chain_id), amounts)| {
+            encode_proof_data_journal_entry(
+                chain_id,
+                *user,
+                *market,
+                amounts,
+                *target_chain_id,
+                false,
+                sequencer_timestamp,
+                chain_config_hash,
+            )
+        })
+        .collect())
+}
+
+/// Validates the block the linking blocks of a request end at, without L1 inclusion.
+///
+/// # Returns
+/// * `Result<(B256, u64), PreflightError>` - The validated block hash and the timestamp of the sequencer-signed
+///   payload, or zero if none was used.
+fn validate_head(
+    request: &ProofDataRequest,
+    head: &RlpHeader<Header>,
+) -> Result<(B256, u64), PreflightError> {
+    let chain_id = request.chain_id;
+    let unsupported = |reason| PreflightError::Unsupported { chain_id, reason };
+    let commitment = request
+        .sequencer_commitment_opstack
+        .as_ref()
+        .ok_or(unsupported("missing sequencer commitment"));
+    match chain_id {
+        ETHEREUM_CHAIN_ID | ETHEREUM_SEPOLIA_CHAIN_ID => {
+            // Ethereum is validated through the L1Block contract of Optimism.
+            let op_chain_id = if chain_id == ETHEREUM_CHAIN_ID {
+                OPTIMISM_CHAIN_ID
+            } else {
+                OPTIMISM_SEPOLIA_CHAIN_ID
+            };
+            let env_op = request
+                .env_input_opstack_for_l1_block_call
+                .clone()
+                .ok_or(unsupported("missing l1 block input"))?
+                .into_env(get_eth_chain_spec(chain_id));
+            let payload =
+                check_scheduled_commitment(op_chain_id, commitment?, env_op.commitment().digest)?;
+            let ethereum_hash = Contract::new(L1_BLOCK_ADDRESS_OPSTACK, &env_op)
+                .call_builder(&IL1Block::hashCall {})
+                .try_call()
+                .map_err(|err| call_error(op_chain_id, err))?;
+            Ok((ethereum_hash, payload.timestamp))
+        }
+        OPTIMISM_CHAIN_ID | OPTIMISM_SEPOLIA_CHAIN_ID | BASE_CHAIN_ID | BASE_SEPOLIA_CHAIN_ID => {
+            let payload = check_scheduled_commitment(chain_id, commitment?, head.hash_slow())?;
+            Ok((payload.block_hash, payload.timestamp))
+        }
+        LINEA_CHAIN_ID | LINEA_SEPOLIA_CHAIN_ID => {
+            // Validator sets read from L1 need the Ethereum environment of L1 inclusion.
+            let consensus = get_linea_consensus(chain_id, head.number);
+            check_linea_seals(&consensus, chain_id, head.inner(), None)?;
+            Ok((get_block_hash(chain_id, head.inner()), 0))
+        }
+        _ => match request.opstack_rollup_config.as_ref() {
+            Some(rollup_config) => {
+                let payload = check_sequencer_commitment(
+                    rollup_config.l2_chain_id,
+                    &rollup_config.hardforks(),
+                    rollup_config.unsafe_block_signer,
+                    commitment?,
+                    head.hash_slow(),
+                )?;
+                Ok((payload.block_hash, payload.timestamp))
+            }
+            None => Err(unsupported("requires l1 inclusion")),
+        },
+    }
+}
+
+/// Checks the commitment of a built-in OpStack chain against the sequencer scheduled for its block.
+fn check_scheduled_commitment(
+    chain_id: u64,
+    commitment: &SequencerCommitment,
+    env_block_hash: B256,
+) -> Result<OpStackPayloadHeader, PreflightError> {
+    let hardforks = get_opstack_hardforks(chain_id);
+    let payload = decode_opstack_payload_with_hardforks(&hardforks, &commitment.data)
+        .map_err(SequencerCommitmentError::Payload)?;
+    let sequencer = get_expected_sequencer(chain_id, payload.block_number);
+    Ok(check_sequencer_commitment(
+        chain_id,
+        &hardforks,
+        sequencer,
+        commitment,
+        env_block_hash,
+    )?)
+}
+
+/// A proof data request assembled and validated on the host.
+#[derive(Clone)]
+pub struct PreflightOutput {
+    pub request: ProofDataRequest,
+    /// Journal entries the guest commits for the request.
+    pub journal_entries: Vec<Bytes>,
+    /// The request serialized with [`serialize_request`].
+    pub input: Vec<u8>,
+}
+
+/// Assembles the proof data request of one source chain, validated without L1 inclusion.
+///
+/// Ethereum is validated through the L1Block contract of Optimism, OpStack chains through their sequencer
+/// commitment and Linea through the sequencer seal of its latest block. Scroll only supports L1 inclusion and
+/// is not supported.
+pub struct ProofDataRequestBuilder<'a, P> {
+    provider: &'a P,
+    chain_id: u64,
+    account: Vec<Address>,
+    asset: Vec<Address>,
+    target_chain_ids: Vec<u64>,
+    opstack_rollup_config: Option<OpStackRollupConfig>,
+    target_opstack_rollup_configs: Vec<OpStackRollupConfig>,
+}
+
+impl<'a, P: ChainProvider> ProofDataRequestBuilder<'a, P> {
+    /// Creates a builder for the entries of the given accounts, markets and target chains.
+    pub fn new(
+        provider: &'a P,
+        chain_id: u64,
+        account: Vec<Address>,
+        asset: Vec<Address>,
+        target_chain_ids: Vec<u64>,
+    ) -> Self {
+        Self {
+            provider,
+            chain_id,
+            account,
+            asset,
+            target_chain_ids,
+            opstack_rollup_config: None,
+            target_opstack_rollup_configs: Vec::new(),
+        }
+    }
+
+    /// Validates the chain with the given rollup config instead of built-in parameters.
+    pub fn with_rollup_config(self, opstack_rollup_config: OpStackRollupConfig) -> Self {
+        Self {
+            opstack_rollup_config: Some(opstack_rollup_config),
+            ..self
+        }
+    }
+
+    /// Allows entries to target the chains of the given rollup configs.
+    pub fn with_target_rollup_configs(
+        self,
+        target_opstack_rollup_configs: Vec<OpStackRollupConfig>,
+    ) -> Self {
+        Self {
+            target_opstack_rollup_configs,
+            ..self
+        }
+    }
+
+    /// Fetches the inputs of the request.
+    ///
+    /// # Returns
+    /// * `Result<ProofDataRequest, PreflightError>` - The request; see [`Self::preflight`] to also validate it.
+    pub fn build(&self) -> Result<ProofDataRequest, PreflightError> {
+        let chain_id = self.chain_id;
+        check_target_chain_ids(
+            chain_id,
+            self.opstack_rollup_config.as_ref(),
+            &self.target_opstack_rollup_configs,
+            self.account.len(),
+            self.asset.len(),
+            &self.target_chain_ids,
+        )?;
+
+        // Find the block whose hash the guest validates, and the inputs validating it.
+        let mut sequencer_commitment_opstack = None;
+        let mut env_input_opstack_for_l1_block_call = None;
+        let head = match chain_id {
+            ETHEREUM_CHAIN_ID | ETHEREUM_SEPOLIA_CHAIN_ID => {
+                let op_chain_id = if chain_id == ETHEREUM_CHAIN_ID {
+                    OPTIMISM_CHAIN_ID
+                } else {
+                    OPTIMISM_SEPOLIA_CHAIN_ID
+                };
+                let commitment = self.provider.sequencer_commitment(op_chain_id)?;
+                let payload = decode_opstack_payload(op_chain_id, &commitment.data)
+                    .map_err(SequencerCommitmentError::Payload)?;
+                let chain_spec = get_eth_chain_spec(chain_id);
+                let input =
+                    self.provider
+                        .l1_block_input(op_chain_id, chain_spec, payload.block_number)?;
+                let env_op = input.clone().into_env(chain_spec);
+                let ethereum_hash = Contract::new(L1_BLOCK_ADDRESS_OPSTACK, &env_op)
+                    .call_builder(&IL1Block::hashCall {})
+                    .try_call()
+                    .map_err(|err| call_error(op_chain_id, err))?;
+                sequencer_commitment_opstack = Some(commitment);
+                env_input_opstack_for_l1_block_call = Some(input);
+                self.provider.header_by_hash(chain_id, ethereum_hash)?
+            }
+            OPTIMISM_CHAIN_ID
+            | OPTIMISM_SEPOLIA_CHAIN_ID
+            | BASE_CHAIN_ID
+            | BASE_SEPOLIA_CHAIN_ID => {
+                let commitment = self.provider.sequencer_commitment(chain_id)?;
+                let payload = decode_opstack_payload(chain_id, &commitment.data)
+                    .map_err(SequencerCommitmentError::Payload)?;
+                sequencer_commitment_opstack = Some(commitment);
+                self.provider
+                    .header_by_number(chain_id, payload.block_number)?
+            }
+            LINEA_CHAIN_ID | LINEA_SEPOLIA_CHAIN_ID => {
+                let block_number = self.provider.latest_block_number(chain_id)?;
+                self.provider.header_by_number(chain_id, block_number)?
+            }
+            _ => match self.opstack_rollup_config.as_ref() {
+                Some(rollup_config) => {
+                    let commitment = self.provider.sequencer_commitment(chain_id)?;
+                    let payload = decode_opstack_payload_with_hardforks(
+                        &rollup_config.hardforks(),
+                        &commitment.data,
+                    )
+                    .map_err(SequencerCommitmentError::Payload)?;
+                    sequencer_commitment_opstack = Some(commitment);
+                    self.provider
+                        .header_by_number(chain_id, payload.block_number)?
+                }
+                None => {
+                    return Err(PreflightError::Unsupported {
+                        chain_id,
+                        reason: "requires l1 inclusion or is not supported",
+                    })
+                }
+            },
+        };
+
+        // Link the view call block to the validated block.
+        let policy = match self.opstack_rollup_config.as_ref() {
+            Some(rollup_config) => rollup_config.reorg_protection_policy(),
+            None => get_reorg_protection_policy(chain_id),
+        };
+        let linking_blocks = collect_linking_blocks(self.provider, chain_id, &policy, head)?;
+        let view_call_block_number = linking_blocks[0].number - 1;
+
+        // Preflight the view call at the historical block.
+        let chain_spec = match self.opstack_rollup_config.as_ref() {
+            Some(rollup_config) => get_eth_chain_spec(rollup_config.l1_chain_id),
+            None => get_eth_chain_spec(chain_id),
+        };
+        let market_registry = if self.opstack_rollup_config.is_some() {
+            None
+        } else {
+            get_market_registry(chain_id)
+        };
+        let multicall = proof_data_multicall(
+            &self.account,
+            &self.asset,
+            &self.target_chain_ids,
+            market_registry,
+        );
+        let env_input_for_viewcall = self.provider.proof_data_input(
+            chain_id,
+            chain_spec,
+            view_call_block_number,
+            &multicall,
+        )?;
+
+        Ok(ProofDataRequest {
+            chain_id,
+            account: self.account.clone(),
+            asset: self.asset.clone(),
+            target_chain_ids: self.target_chain_ids.clone(),
+            env_input_for_viewcall: Some(env_input_for_viewcall),
+            sequencer_commitment_opstack,
+            env_input_opstack_for_l1_block_call,
+            linking_blocks,
+            env_input_eth_for_l1_inclusion: None,
+            env_input_opstack_for_viewcall_with_l1_inclusion: None,
+            sequencer_commitment_opstack_2: None,
+            env_input_opstack_for_l1_block_call_2: None,
+            ethereum_finality_proof: None,
+            ancestry_claim: None,
+            message_passer_proof: None,
+            super_root_proof: None,
+            scroll_batch_index: None,
+            opstack_rollup_config: self.opstack_rollup_config.clone(),
+            target_opstack_rollup_configs: self.target_opstack_rollup_configs.clone(),
+            env_input_eth_for_l1_signer: None,
+        })
+    }
+
+    /// Fetches the inputs of the request, dry-runs it and serializes it.
+    ///
+    /// # Returns
+    /// * `Result<PreflightOutput, PreflightError>` - The request, its journal entries and the guest input.
+    pub fn preflight(&self) -> Result<PreflightOutput, PreflightError> {
+        let request = self.build()?;
+        let journal_entries = dry_run(&request)?;
+        let input = serialize_request(&request)?;
+        Ok(PreflightOutput {
+            request,
+            journal_entries,
+            input,
+        })
+    }
+}
+
+/// Fetches the linking blocks ending at `head` that satisfy the reorg protection policy.
+///
+/// # Arguments
+/// * `provider` - The provider to fetch the headers from.
+/// * `chain_id` - The chain ID of the headers.
+/// * `policy` - The reorg protection policy to satisfy.
+/// * `head` - The last linking block, whose hash the guest validates.
+///
+/// # Returns
+/// * `Result<Vec<RlpHeader<Header>>, PreflightError>` - The linking blocks in ascending order; the view call
+///   runs on the parent of the first one.
+pub fn collect_linking_blocks<P: ChainProvider>(
+    provider: &P,
+    chain_id: u64,
+    policy: &ReorgProtectionPolicy,
+    head: RlpHeader<Header>,
+) -> Result<Vec<RlpHeader<Header>>, PreflightError> {
+    let head_timestamp = head.timestamp;
+    let mut linking_blocks = vec![head];
+    loop {
+        let first_number = linking_blocks[linking_blocks.len() - 1].number;
+        if first_number == 0 {
+            return Err(PreflightError::ChainTooShort { chain_id });
+        }
+        let historical = provider.header_by_number(chain_id, first_number - 1)?;
+        let enough_blocks = policy
+            .min_blocks
+            .is_none_or(|min_blocks| linking_blocks.len() as u64 >= min_blocks);
+        let enough_time = policy
+            .min_elapsed_seconds
+            .is_none_or(|min_elapsed_seconds| {
+                head_timestamp.saturating_sub(historical.timestamp) >= min_elapsed_seconds
+            });
+        if enough_blocks && enough_time {
+            break;
+        }
+        if linking_blocks.len() as u64 >= policy.max_blocks {
+            return Err(PreflightError::TooManyLinkingBlocks {
+                chain_id,
+                max: policy.max_blocks,
+            });
+        }
+        linking_blocks.push(historical);
+    }
+    linking_blocks.reverse();
+    Ok(linking_blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a hash-linked chain of headers starting at block zero.
+    fn linked_headers(length: u64, block_time: u64) -> Vec<RlpHeader<Header>> {
+        let mut headers: Vec<RlpHeader<Header>> = Vec::new();
+        for number in 0..length {
+            let parent_hash = headers
+                .last()
+                .map_or(B256::ZERO, |parent| parent.hash_slow());
+            headers.push(RlpHeader::new(Header {
+                number,
+                timestamp: number * block_time,
+                parent_hash,
+                ..Default::default()
+            }));
+        }
+        headers
+    }
+
+    /// Provider serving a hash-linked chain of headers.
+    struct HeaderChain(Vec<RlpHeader<Header>>);
+
+    impl HeaderChain {
+        fn new(length: u64, block_time: u64) -> Self {
+            Self(linked_headers(length, block_time))
+        }
+
+        fn missing(chain_id: u64) -> PreflightError {
+            PreflightError::Provider {
+                chain_id,
+                message: "missing".to_string(),
+            }
+        }
+    }
+
+    impl ChainProvider for HeaderChain {
+        fn latest_block_number(&self, _chain_id: u64) -> Result<u64, PreflightError> {
+            Ok(self.0.len() as u64 - 1)
+        }
+
+        fn header_by_number(
+            &self,
+            chain_id: u64,
+            block_number: u64,
+        ) -> Result<RlpHeader<Header>, PreflightError> {
+            let header = self.0.get(block_number as usize);
+            header.cloned().ok_or(Self::missing(chain_id))
+        }
+
+        fn header_by_hash(
+            &self,
+            chain_id: u64,
+            block_hash: B256,
+        ) -> Result<RlpHeader<Header>, PreflightError> {
+            let header = self
+                .0
+                .iter()
+                .find(|header| header.hash_slow() == block_hash);
+            header.cloned().ok_or(Self::missing(chain_id))
+        }
+
+        fn sequencer_commitment(
+            &self,
+            chain_id: u64,
+        ) -> Result<SequencerCommitment, PreflightError> {
+            Err(Self::missing(chain_id))
+        }
+
+        fn proof_data_input(
+            &self,
+            chain_id: u64,
+            _chain_spec: &EthChainSpec,
+            _block_number: u64,
+            _multicall: &IMulticall3::aggregate3Call,
+        ) -> Result<EthEvmInput, PreflightError> {
+            Err(Self::missing(chain_id))
+        }
+
+        fn l1_block_input(
+            &self,
+            chain_id: u64,
+            _chain_spec: &EthChainSpec,
+            _block_number: u64,
+        ) -> Result<EthEvmInput, PreflightError> {
+            Err(Self::missing(chain_id))
+        }
+    }
+
+    fn assert_linked(chain: &HeaderChain, policy: &ReorgProtectionPolicy, head: u64) -> Vec<u64> {
+        let head = chain.0[head as usize].clone();
+        let head_hash = head.hash_slow();
+        let linking_blocks =
+            collect_linking_blocks(chain, ETHEREUM_CHAIN_ID, policy, head).unwrap();
+        let historical = &chain.0[linking_blocks[0].number as usize - 1];
+        assert_eq!(
+            check_linking_blocks(
+                ETHEREUM_CHAIN_ID,
+                policy,
+                historical.hash_slow(),
+                historical.inner(),
+                &linking_blocks,
+                head_hash,
+            ),
+            Ok(())
+        );
+        linking_blocks.iter().map(|header| header.number).collect()
+    }
+
+    #[test]
+    fn collect_linking_blocks_for_policies() {
+        let chain = HeaderChain::new(20, 12);
+        assert_eq!(
+            assert_linked(&chain, &ReorgProtectionPolicy::blocks(5), 19),
+            vec![15, 16, 17, 18, 19]
+        );
+        assert_eq!(
+            assert_linked(&chain, &ReorgProtectionPolicy::elapsed(30), 19),
+            vec![17, 18, 19]
+        );
+        assert_eq!(
+            assert_linked(
+                &chain,
+                &ReorgProtectionPolicy::blocks_and_elapsed(2, 30),
+                19
+            ),
+            vec![17, 18, 19]
+        );
+    }
+
+    #[test]
+    fn collect_linking_blocks_unsatisfiable() {
+        let chain = HeaderChain::new(20, 12);
+        let head = chain.0[19].clone();
+        assert_eq!(
+            collect_linking_blocks(
+                &chain,
+                ETHEREUM_CHAIN_ID,
+                &ReorgProtectionPolicy::blocks(25),
+                head.clone(),
+            )
+            .unwrap_err(),
+            PreflightError::ChainTooShort {
+                chain_id: ETHEREUM_CHAIN_ID
+            }
+        );
+        assert_eq!(
+            collect_linking_blocks(
+                &chain,
+                ETHEREUM_CHAIN_ID,
+                &ReorgProtectionPolicy::blocks(10).with_max_blocks(5),
+                head,
+            )
+            .unwrap_err(),
+            PreflightError::TooManyLinkingBlocks {
+                chain_id: ETHEREUM_CHAIN_ID,
+                max: 5
+            }
+        );
+    }
+
+    #[test]
+    fn file_provider_fixtures() {
+        let root = std::env::temp_dir().join(format!("malda-preflight-{}", std::process::id()));
+        let recorder = RecordingProvider::new(HeaderChain::new(3, 12), &root);
+        let header = recorder.header_by_number(ETHEREUM_CHAIN_ID, 2).unwrap();
+        recorder
+            .header_by_hash(ETHEREUM_CHAIN_ID, header.hash_slow())
+            .unwrap();
+        recorder.latest_block_number(ETHEREUM_CHAIN_ID).unwrap();
+
+        let fixtures = FileProvider::new(&root);
+        let by_number = fixtures.header_by_number(ETHEREUM_CHAIN_ID, 2).unwrap();
+        let by_hash = fixtures
+            .header_by_hash(ETHEREUM_CHAIN_ID, header.hash_slow())
+            .unwrap();
+        assert_eq!(by_number.hash_slow(), header.hash_slow());
+        assert_eq!(by_hash.hash_slow(), header.hash_slow());
+        assert_eq!(fixtures.latest_block_number(ETHEREUM_CHAIN_ID), Ok(2));
+        assert!(matches!(
+            fixtures.header_by_number(ETHEREUM_CHAIN_ID, 1),
+            Err(PreflightError::Provider { .. })
+        ));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    /// Builds a request for the given chain without any inputs.
+    fn empty_request(chain_id: u64, target_chain_ids: Vec<u64>) -> ProofDataRequest {
+        ProofDataRequest {
+            chain_id,
+            account: vec![Address::ZERO; target_chain_ids.len()],
+            asset: vec![Address::ZERO; target_chain_ids.len()],
+            target_chain_ids,
+            env_input_for_viewcall: None,
+            sequencer_commitment_opstack: None,
+            env_input_opstack_for_l1_block_call: None,
+            linking_blocks: Vec::new(),
+            env_input_eth_for_l1_inclusion: None,
+            env_input_opstack_for_viewcall_with_l1_inclusion: None,
+            sequencer_commitment_opstack_2: None,
+            env_input_opstack_for_l1_block_call_2: None,
+            ethereum_finality_proof: None,
+            ancestry_claim: None,
+            message_passer_proof: None,
+            super_root_proof: None,
+            scroll_batch_index: None,
+            opstack_rollup_config: None,
+            target_opstack_rollup_configs: Vec::new(),
+            env_input_eth_for_l1_signer: None,
+        }
+    }
+
+    #[test]
+    fn dry_run_typed_errors() {
+        assert_eq!(
+            dry_run(&empty_request(OPTIMISM_CHAIN_ID, vec![OPTIMISM_CHAIN_ID])).unwrap_err(),
+            PreflightError::TargetChain(TargetChainError::TargetIsSource {
+                index: 0,
+                chain_id: OPTIMISM_CHAIN_ID
+            })
+        );
+        assert_eq!(
+            dry_run(&empty_request(SCROLL_CHAIN_ID, vec![LINEA_CHAIN_ID])).unwrap_err(),
+            PreflightError::Unsupported {
+                chain_id: SCROLL_CHAIN_ID,
+                reason: "missing view call input"
+            }
+        );
+        assert!(matches!(
+            dry_run(&empty_request(12_345, vec![LINEA_CHAIN_ID])),
+            Err(PreflightError::Unsupported {
+                chain_id: 12_345,
+                ..
+            })
+        ));
+    }
+}
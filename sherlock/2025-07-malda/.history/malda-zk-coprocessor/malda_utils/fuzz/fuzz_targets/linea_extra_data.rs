@@ -0,0 +1,40 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![no_main]
+
+use alloy_primitives::Bytes;
+use libfuzzer_sys::fuzz_target;
+use malda_utils::validators::{
+    parse_linea_extra_data, LineaExtraDataError, LINEA_EXTRA_DATA_SEAL_LEN,
+    LINEA_EXTRA_DATA_VANITY_LEN, LINEA_EXTRA_DATA_VERSIONS,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let extra_data = Bytes::copy_from_slice(data);
+    let expected = LINEA_EXTRA_DATA_VANITY_LEN + LINEA_EXTRA_DATA_SEAL_LEN;
+    match parse_linea_extra_data(&extra_data) {
+        Ok(parsed) => {
+            assert_eq!(data.len(), expected);
+            assert!(LINEA_EXTRA_DATA_VERSIONS.contains(&parsed.version()));
+            assert_eq!(parsed.vanity.len(), LINEA_EXTRA_DATA_VANITY_LEN);
+            assert_eq!([&parsed.vanity[..], &parsed.signature[..]].concat(), data);
+        }
+        Err(LineaExtraDataError::InvalidLength { length, .. }) => {
+            assert_ne!(data.len(), expected);
+            assert_eq!(length, data.len());
+        }
+        Err(LineaExtraDataError::UnsupportedVersion { version }) => {
+            assert_eq!(data.len(), expected);
+            assert_eq!(version, data[0]);
+            assert!(!LINEA_EXTRA_DATA_VERSIONS.contains(&version));
+        }
+    }
+});
@@ -0,0 +1,28 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![no_main]
+
+use alloy_consensus::Header;
+use libfuzzer_sys::fuzz_target;
+use malda_utils::constants::{LINEA_CHAIN_ID, LINEA_SEPOLIA_CHAIN_ID};
+use malda_utils::validators::{check_linea_seals, get_linea_consensus};
+
+// Arbitrary headers must be rejected with a typed error, never a panic.
+fuzz_target!(|input: (bool, Header)| {
+    let (sepolia, header) = input;
+    let chain_id = if sepolia {
+        LINEA_SEPOLIA_CHAIN_ID
+    } else {
+        LINEA_CHAIN_ID
+    };
+    let consensus = get_linea_consensus(chain_id, header.number);
+    let _ = check_linea_seals(&consensus, chain_id, &header, None);
+});
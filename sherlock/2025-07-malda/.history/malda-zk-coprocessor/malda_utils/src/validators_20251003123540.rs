@@ -19,6 +19,7 @@
 //! - Linea block validation through sequencer signatures
 //! - OpStack (Optimism/Base) validation through sequencer commitments
 //! - Ethereum L1 block validation through OpStack L2s
+//! - Scroll block validation through batches finalized on L1
 //! - Chain length validation for reorg protection
 //!
 //! Supported networks include:
@@ -26,63 +27,156 @@
 //! - Optimism - Mainnet and Sepolia
 //! - Base - Mainnet and Sepolia
 //! - Linea - Mainnet and Sepolia
+//! - Scroll - Mainnet and Sepolia
 
 use crate::constants::*;
 use crate::cryptography::{recover_signer, signature_from_bytes};
 use crate::types::*;
 use alloy_consensus::Header;
-use alloy_primitives::{Address, Bytes, B256, U256};
-use alloy_sol_types::SolValue;
-use risc0_op_steel::optimism::{OpEvmFactory, OpEvmInput, OP_MAINNET_CHAIN_SPEC};
+use alloy_primitives::{address, keccak256, Address, Bytes, B256, U256};
+use alloy_sol_types::{sol, SolCall, SolValue};
+use alloy_trie::{proof::verify_proof, Nibbles, TrieAccount};
+use revm::primitives::hardfork::SpecId;
+use risc0_op_steel::optimism::{
+    OpChainSpec, OpEvmFactory, OpEvmInput, OP_MAINNET_CHAIN_SPEC, OP_SEPOLIA_CHAIN_SPEC,
+};
 use risc0_steel::EvmFactory;
 use risc0_steel::{
-    ethereum::{EthEvmFactory, EthEvmInput, ETH_MAINNET_CHAIN_SPEC},
+    config::ForkCondition,
+    ethereum::{
+        EthChainSpec, EthEvmFactory, EthEvmInput, ETH_MAINNET_CHAIN_SPEC, ETH_SEPOLIA_CHAIN_SPEC,
+    },
     serde::RlpHeader,
     Commitment, Contract, EvmEnv, StateDb,
 };
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
 
 /// Validates and executes proof data queries across multiple accounts and tokens using multicall.
 ///
 /// This function orchestrates the validation of proof data queries for multiple accounts and assets across different EVM chains. It sorts and verifies the relevant parameters, validates block hashes and chain length for reorg protection, and executes a batch multicall to retrieve proof data.
 ///
 /// # Arguments
-/// * `chain_id` - The chain ID to validate against.
-/// * `account` - Vector of account addresses to query.
-/// * `asset` - Vector of token contract addresses to query.
-/// * `target_chain_ids` - Vector of target chain IDs for each account.
-/// * `env_input_for_viewcall` - Optional EVM environment input for the chain.
-/// * `sequencer_commitment_opstack` - Optional sequencer commitment for L2 chains.
-/// * `env_input_opstack_for_l1_block_call` - Optional Optimism environment input for L1 validation.
-/// * `linking_blocks` - Vector of blocks for reorg protection.
+/// * `request` - The proof data request, see [`ProofDataRequest`].
+/// * `l1_hash_assumption` - Optional Ethereum block hash attested by an L1 hash proof; replaces the OpStack
+///   validation of the Ethereum block used for L1 inclusion and is required to read the unsafe block signer
+///   from L1. See [`verify_l1_hash_assumption`].
+/// * `options` - Image IDs of the guests the request may compose with, see [`ProofDataOptions`].
 /// * `output` - Output vector for proof data results.
-/// * `env_input_eth_for_l1_inclusion` - Optional Ethereum environment input for L1 inclusion.
-/// * `env_input_opstack_for_viewcall_with_l1_inclusion` - Optional OpStack environment input for L1 inclusion.
-/// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment for L2 chains.
-/// * `env_input_opstack_for_l1_block_call_2` - Optional second Optimism environment input for L1 validation.
 ///
 /// # Panics
 /// Panics if:
 /// * Chain ID is invalid
+/// * A rollup config is provided for a built-in chain or for a different chain
+/// * The request fails [`check_target_chain_ids`]
 /// * Environment validation fails
 /// * Chain length is insufficient
+/// * A finality proof is provided for a non-Ethereum chain or fails validation
+/// * An ancestry claim is provided together with a finality proof, without an ancestry image ID, or fails
+///   [`validate_chain_length_with_ancestry`]
+/// * The L1 hash assumption fails [`verify_l1_hash_assumption`] or is given without an L1 hash image ID
+/// * An Ethereum environment for the L1 signer is given without an L1 hash assumption or not at its block
 /// * Block hashes don't match
+/// * The L1 inclusion policy of the chain requires L1 inclusion that was not validated
+/// * A market is not listed in the market registry of the chain, if one is configured
 /// * Multicall execution fails
 /// * Return data decoding fails
 pub fn validate_get_proof_data_call(
-    chain_id: u64,
-    account: Vec<Address>,
-    asset: Vec<Address>,
-    target_chain_ids: Vec<u64>,
-    env_input_for_viewcall: Option<EthEvmInput>,
-    sequencer_commitment_opstack: Option<SequencerCommitment>,
-    env_input_opstack_for_l1_block_call: Option<EthEvmInput>,
-    linking_blocks: &Vec<RlpHeader<Header>>,
+    mut request: ProofDataRequest,
+    l1_hash_assumption: Option<L1HashAssumption>,
+    options: &ProofDataOptions,
     output: &mut Vec<Bytes>,
-    env_input_eth_for_l1_inclusion: &Option<EthEvmInput>,
-    env_input_opstack_for_viewcall_with_l1_inclusion: Option<OpEvmInput>,
-    sequencer_commitment_opstack_2: Option<SequencerCommitment>,
-    env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
 ) {
+    let assumed_ethereum_hash = l1_hash_assumption.as_ref().map(|assumption| {
+        let ethereum_chain_id = get_ethereum_chain_id(is_testnet_chain(
+            request.chain_id,
+            request.opstack_rollup_config.as_ref(),
+        ));
+        verify_l1_hash_assumption(
+            options.l1_hash_image_id.expect("l1_hash_image_id is None"),
+            assumption,
+            ethereum_chain_id,
+        )
+    });
+    let eth_env_for_l1_signer = take_l1_signer_env(&mut request, assumed_ethereum_hash);
+    let validated_ethereum_hash = request
+        .env_input_eth_for_l1_inclusion
+        .as_ref()
+        .and(assumed_ethereum_hash);
+    validate_proof_data_call(
+        request,
+        options,
+        output,
+        validated_ethereum_hash,
+        eth_env_for_l1_signer.as_ref(),
+    );
+}
+
+/// Validates and executes proof data queries, reusing an Ethereum block hash that was already validated.
+///
+/// `validated_ethereum_hash` is the Ethereum block hash used for L1 inclusion if it was already validated, and
+/// `eth_env_for_l1_signer` an Ethereum environment at a block attested by an L1 hash assumption, from which
+/// OpStack chains validated without L1 inclusion read their unsafe block signer; see
+/// [`validate_get_proof_data_call`] for the other arguments.
+fn validate_proof_data_call(
+    request: ProofDataRequest,
+    options: &ProofDataOptions,
+    output: &mut Vec<Bytes>,
+    validated_ethereum_hash: Option<B256>,
+    eth_env_for_l1_signer: Option<&EvmEnv<StateDb, EthEvmFactory, Commitment>>,
+) {
+    let ProofDataRequest {
+        chain_id,
+        account,
+        asset,
+        target_chain_ids,
+        env_input_for_viewcall,
+        sequencer_commitment_opstack,
+        env_input_opstack_for_l1_block_call,
+        linking_blocks,
+        env_input_eth_for_l1_inclusion,
+        env_input_opstack_for_viewcall_with_l1_inclusion,
+        sequencer_commitment_opstack_2,
+        env_input_opstack_for_l1_block_call_2,
+        ethereum_finality_proof,
+        ancestry_claim,
+        message_passer_proof,
+        super_root_proof,
+        scroll_batch_index,
+        opstack_rollup_config,
+        target_opstack_rollup_configs,
+        env_input_eth_for_l1_signer: _,
+    } = request;
+
+    // Rollup configs onboard additional OpStack chains; they must not override the built-in chains.
+    if let Some(rollup_config) = opstack_rollup_config.as_ref() {
+        assert_eq!(
+            rollup_config.l2_chain_id, chain_id,
+            "rollup config chain id mismatch"
+        );
+        assert!(
+            !is_builtin_chain(chain_id),
+            "rollup config for built-in chain"
+        );
+    }
+    let chain_config_hash = opstack_rollup_config
+        .as_ref()
+        .map_or(B256::ZERO, OpStackRollupConfig::config_hash);
+
+    // Reject requests whose journal entries the destination chains would never accept.
+    if let Err(err) = check_target_chain_ids(
+        chain_id,
+        opstack_rollup_config.as_ref(),
+        &target_opstack_rollup_configs,
+        account.len(),
+        asset.len(),
+        &target_chain_ids,
+    ) {
+        panic!("{err}");
+    }
+
     // Sort and verify all relevant parameters for the proof data call, including environment and block headers.
     let (
         env_for_viewcall,
@@ -96,42 +190,148 @@ pub fn validate_get_proof_data_call(
     ) = sort_and_verify_relevant_params(
         chain_id,
         env_input_for_viewcall,
-        linking_blocks,
-        env_input_eth_for_l1_inclusion,
+        &linking_blocks,
+        &env_input_eth_for_l1_inclusion,
         env_input_opstack_for_viewcall_with_l1_inclusion,
+        opstack_rollup_config.as_ref(),
     );
 
+    // Keep the historical block header for the structural checks on the linking blocks.
+    let historical_header = env_header_to_validate.clone();
+
+    // Keep the OpStack L2 header for output root recomputation when validating L1 inclusion.
+    let op_env_header = op_env_for_viewcall_with_l1_inclusion
+        .as_ref()
+        .map(|op_env| op_env.header().inner().inner().clone());
+
+    // Commit the timestamp of the sequencer-signed payload for proofs without L1 inclusion to the journal, so
+    // that the consumer can reject stale payloads against its own block timestamp.
+    // Only OpStack chains and Ethereum, which is validated through an Optimism sequencer commitment, source
+    // their block hash from a sequencer commitment.
+    let sequencer_chain_id = match chain_id {
+        ETHEREUM_CHAIN_ID => Some(OPTIMISM_CHAIN_ID),
+        ETHEREUM_SEPOLIA_CHAIN_ID => Some(OPTIMISM_SEPOLIA_CHAIN_ID),
+        OPTIMISM_CHAIN_ID | OPTIMISM_SEPOLIA_CHAIN_ID | BASE_CHAIN_ID | BASE_SEPOLIA_CHAIN_ID => {
+            Some(chain_id)
+        }
+        _ if opstack_rollup_config.is_some() => Some(chain_id),
+        _ => None,
+    };
+    let sequencer_timestamp = match sequencer_chain_id {
+        Some(sequencer_chain_id) if !validate_l1_inclusion => {
+            sequencer_commitment_opstack.as_ref().map(|commitment| {
+                let hardforks = opstack_rollup_config.as_ref().map_or_else(
+                    || get_opstack_hardforks(sequencer_chain_id),
+                    OpStackRollupConfig::hardforks,
+                );
+                decode_opstack_payload_with_hardforks(&hardforks, &commitment.data)
+                    .expect("Failed to decode sequencer commitment payload")
+                    .timestamp
+            })
+        }
+        _ => None,
+    };
+
     // Validate the block hash for the given chain and environment.
     let validated_block_hash = get_validated_block_hash(
         chain_id,
         env_header_to_validate,
         sequencer_commitment_opstack,
         env_input_opstack_for_l1_block_call,
-        env_input_eth_for_l1_inclusion,
+        &env_input_eth_for_l1_inclusion,
         block_header_to_validate,
         validate_l1_inclusion,
         op_env_commitment.as_ref(),
         sequencer_commitment_opstack_2,
         env_input_opstack_for_l1_block_call_2,
+        op_env_header.as_ref(),
+        message_passer_proof.as_ref(),
+        super_root_proof.as_ref(),
+        scroll_batch_index,
+        opstack_rollup_config.as_ref(),
+        validated_ethereum_hash,
+        eth_env_for_l1_signer,
     );
 
-    // Ensure the chain length and hash linking are valid for reorg protection.
-    validate_chain_length(
-        chain_id_for_length_validation,
-        env_header_hash_to_validate,
-        linking_blocks,
-        validated_block_hash,
-    );
+    // Configured chains carry their own reorg protection, unless validated through their L1.
+    let policy = match opstack_rollup_config.as_ref() {
+        Some(rollup_config) if chain_id_for_length_validation == rollup_config.l2_chain_id => {
+            rollup_config.reorg_protection_policy()
+        }
+        _ => get_reorg_protection_policy(chain_id_for_length_validation),
+    };
+
+    // Ensure the chain length and hash linking are valid for reorg protection, or, in finality mode,
+    // that the view call block is an ancestor of a finalized checkpoint, or, in ancestry mode, that a
+    // proven ancestry claim links the view call block to the validated block.
+    if let Some(finality_proof) = ethereum_finality_proof.as_ref() {
+        assert!(
+            chain_id == ETHEREUM_CHAIN_ID || chain_id == ETHEREUM_SEPOLIA_CHAIN_ID,
+            "finality proof only supported for ethereum"
+        );
+        assert!(
+            ancestry_claim.is_none(),
+            "ancestry claim not supported with finality proof"
+        );
+        validate_chain_to_finalized_checkpoint(
+            chain_id,
+            env_header_hash_to_validate,
+            &historical_header,
+            &linking_blocks,
+            validated_block_hash,
+            finality_proof,
+        );
+    } else if let Some(claim) = ancestry_claim.as_ref() {
+        // The only linking block is the head of the claim, validated above.
+        assert_eq!(
+            linking_blocks.len(),
+            1,
+            "ancestry claim requires the head as the only linking block"
+        );
+        validate_chain_length_with_ancestry(
+            chain_id_for_length_validation,
+            options
+                .ancestry_image_id
+                .expect("ancestry_image_id is None"),
+            &policy,
+            claim,
+            env_header_hash_to_validate,
+            validated_block_hash,
+        );
+    } else {
+        validate_chain_length_with_policy(
+            chain_id_for_length_validation,
+            &policy,
+            env_header_hash_to_validate,
+            &historical_header,
+            &linking_blocks,
+            validated_block_hash,
+        );
+    }
+
+    // Keep the markets and target chains of the entries for evaluating the L1 inclusion policy.
+    let markets = asset.clone();
+    let entry_target_chain_ids = target_chain_ids.clone();
+
+    // Only attest to listed markets on chains with a market registry.
+    let market_registry = if opstack_rollup_config.is_some() {
+        None
+    } else {
+        get_market_registry(chain_id)
+    };
 
     // Execute the batch multicall to retrieve proof data, using the appropriate environment.
-    if op_env_for_viewcall_with_l1_inclusion.is_some() {
+    let amounts = if let Some(op_env_for_viewcall) = op_env_for_viewcall_with_l1_inclusion {
         batch_call_get_proof_data(
             chain_id,
             account,
             asset,
             target_chain_ids,
-            op_env_for_viewcall_with_l1_inclusion.unwrap(),
+            op_env_for_viewcall,
             validate_l1_inclusion,
+            sequencer_timestamp.unwrap_or_default(),
+            chain_config_hash,
+            market_registry,
             output,
         )
     } else {
@@ -142,11 +342,635 @@ pub fn validate_get_proof_data_call(
             target_chain_ids,
             env_for_viewcall,
             validate_l1_inclusion,
+            sequencer_timestamp.unwrap_or_default(),
+            chain_config_hash,
+            market_registry,
+            output,
+        )
+    };
+
+    // Fail the proof if the returned amounts or target chains require L1 inclusion that was not validated.
+    // Configured chains are extension chains, which only target the host chain.
+    let rules: &[L1InclusionRule] = if opstack_rollup_config.is_some() {
+        &[]
+    } else {
+        get_l1_inclusion_rules(chain_id)
+    };
+    if let Err(err) = check_l1_inclusion_policy(
+        rules,
+        validate_l1_inclusion,
+        &markets,
+        &entry_target_chain_ids,
+        &amounts,
+    ) {
+        panic!("{err}");
+    }
+}
+
+sol! {
+    /// Market listing getter of the Operator on the host chain. Extension chains have no Operator, and their
+    /// gateways expose no listing getter.
+    interface IMarketRegistry {
+        function isMarketListed(address market) external view returns (bool);
+    }
+}
+
+/// Operator of the Linea host chain.
+pub const LINEA_OPERATOR: Address = address!("05bd298c0c3f34b541b42f867baf6707911be437");
+/// Operator of the Linea Sepolia host chain.
+pub const LINEA_SEPOLIA_OPERATOR: Address = address!("389cc3d08305c3daaf19b2bf2ec7dd7f66d68da8");
+
+/// Returns the market registry of the given chain, if markets are checked against one.
+///
+/// Markets are only checked on the host chains, against their Operator.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID the proof is generated for.
+///
+/// # Returns
+/// * `Option<Address>` - The registry exposing `isMarketListed`, or `None` if markets are not checked.
+///
+/// # Panics
+/// Panics if the chain ID is invalid or unsupported.
+pub fn get_market_registry(chain_id: u64) -> Option<Address> {
+    match chain_id {
+        ETHEREUM_CHAIN_ID
+        | ETHEREUM_SEPOLIA_CHAIN_ID
+        | OPTIMISM_CHAIN_ID
+        | OPTIMISM_SEPOLIA_CHAIN_ID
+        | BASE_CHAIN_ID
+        | BASE_SEPOLIA_CHAIN_ID
+        | SCROLL_CHAIN_ID
+        | SCROLL_SEPOLIA_CHAIN_ID => None,
+        LINEA_CHAIN_ID => Some(LINEA_OPERATOR),
+        LINEA_SEPOLIA_CHAIN_ID => Some(LINEA_SEPOLIA_OPERATOR),
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Proof data request for one source chain, the input of [`validate_get_proof_data_call`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProofDataRequest {
+    /// The chain ID to validate against.
+    pub chain_id: u64,
+    /// Accounts to query.
+    pub account: Vec<Address>,
+    /// Token contracts to query, one per account.
+    pub asset: Vec<Address>,
+    /// Target chain ID of each entry.
+    pub target_chain_ids: Vec<u64>,
+    /// EVM environment input for the view call on the chain.
+    pub env_input_for_viewcall: Option<EthEvmInput>,
+    /// Sequencer commitment for OpStack chains, or for Optimism when validating Ethereum.
+    pub sequencer_commitment_opstack: Option<SequencerCommitment>,
+    /// Optimism environment input for the L1Block call when validating Ethereum.
+    pub env_input_opstack_for_l1_block_call: Option<EthEvmInput>,
+    /// Blocks linking the view call block to the validated block, or the head of the ancestry claim.
+    pub linking_blocks: Vec<RlpHeader<Header>>,
+    /// Ethereum environment input for L1 inclusion.
+    pub env_input_eth_for_l1_inclusion: Option<EthEvmInput>,
+    /// OpStack environment input for the view call when validating L1 inclusion.
+    pub env_input_opstack_for_viewcall_with_l1_inclusion: Option<OpEvmInput>,
+    /// Second sequencer commitment, used during sequencer rotations.
+    pub sequencer_commitment_opstack_2: Option<SequencerCommitment>,
+    /// Second Optimism environment input for the L1Block call, used during sequencer rotations.
+    pub env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+    /// For Ethereum chains, replaces the linking-block depth requirement with a proof that the view call block
+    /// is an ancestor of a finalized checkpoint.
+    pub ethereum_finality_proof: Option<EthereumFinalityProof>,
+    /// Ancestry claim proven by the ancestry guest; replaces the linking blocks, which must then only contain
+    /// the block to validate, the head of the claim.
+    pub ancestry_claim: Option<AncestryClaim>,
+    /// L2ToL1MessagePasser account proof, required for OpStack L1 inclusion to recompute the output root
+    /// attested by the dispute game.
+    pub message_passer_proof: Option<MessagePasserAccountProof>,
+    /// Super root preimage, required for OpStack L1 inclusion via super root games.
+    pub super_root_proof: Option<SuperRootProof>,
+    /// Index of the finalized Scroll batch ending at the view call block, required for Scroll.
+    pub scroll_batch_index: Option<u64>,
+    /// Rollup config of an OpStack chain that is not built in; its hash is committed to the journal.
+    pub opstack_rollup_config: Option<OpStackRollupConfig>,
+    /// Rollup configs of the configured OpStack chains that entries may target.
+    pub target_opstack_rollup_configs: Vec<OpStackRollupConfig>,
+    /// Ethereum environment at the block of the L1 hash assumption, used by built-in OpStack chains validated
+    /// without L1 inclusion to read their unsafe block signer from L1 instead of the compiled-in sequencer
+    /// schedule.
+    pub env_input_eth_for_l1_signer: Option<EthEvmInput>,
+}
+
+/// Takes the Ethereum environment for the L1 signer out of a request and checks it against the L1 hash assumption.
+///
+/// The signer read from L1 replaces the compiled-in sequencer schedule, so the Ethereum block can't be validated
+/// through an OpStack sequencer commitment, which relies on that schedule: a rotation would break both.
+///
+/// # Panics
+/// Panics if the request has an Ethereum environment for the L1 signer but there is no L1 hash assumption, or
+/// the environment is not at the block of the assumption.
+fn take_l1_signer_env(
+    request: &mut ProofDataRequest,
+    assumed_ethereum_hash: Option<B256>,
+) -> Option<EvmEnv<StateDb, EthEvmFactory, Commitment>> {
+    let env_input = request.env_input_eth_for_l1_signer.take()?;
+    let assumed_ethereum_hash =
+        assumed_ethereum_hash.expect("l1 signer requires an l1 hash assumption");
+    let ethereum_chain_id = get_ethereum_chain_id(is_testnet_chain(
+        request.chain_id,
+        request.opstack_rollup_config.as_ref(),
+    ));
+    let eth_env = env_input.into_env(get_eth_chain_spec(ethereum_chain_id));
+    assert_eq!(
+        eth_env.header().seal(),
+        assumed_ethereum_hash,
+        "Ethereum hash mismatch l1 signer"
+    );
+    Some(eth_env)
+}
+
+/// Image IDs of the guests whose proofs a proof data request may compose with.
+///
+/// Image IDs must be constants compiled into the calling guest and never be read from its input, as otherwise
+/// any guest could vouch for the composed claims. Requests using a claim whose image ID is not set are rejected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProofDataOptions {
+    /// Image ID of the L1 hash guest, see [`verify_l1_hash_assumption`].
+    pub l1_hash_image_id: Option<[u32; 8]>,
+    /// Image ID of the ancestry guest, see [`validate_chain_length_with_ancestry`].
+    pub ancestry_image_id: Option<[u32; 8]>,
+}
+
+impl ProofDataOptions {
+    /// Accepts L1 hash assumptions proven by the given guest.
+    pub const fn with_l1_hash_image_id(self, l1_hash_image_id: [u32; 8]) -> Self {
+        Self {
+            l1_hash_image_id: Some(l1_hash_image_id),
+            ..self
+        }
+    }
+
+    /// Accepts ancestry claims proven by the given guest.
+    pub const fn with_ancestry_image_id(self, ancestry_image_id: [u32; 8]) -> Self {
+        Self {
+            ancestry_image_id: Some(ancestry_image_id),
+            ..self
+        }
+    }
+}
+
+/// Validates proof data requests for several source chains in a single run.
+///
+/// Each chain is validated once, and the Ethereum block hash used for L1 inclusion is validated once, via
+/// OpStack or an L1 hash assumption, and shared by all requests validating L1 inclusion or reading their
+/// unsafe block signer from L1, which must therefore all use the same Ethereum block. Reading the signer from
+/// L1 requires the L1 hash assumption. The journal entries of all requests are appended to `output` in request
+/// order.
+///
+/// # Arguments
+/// * `requests` - The proof data requests, at most one per source chain.
+/// * `sequencer_commitment_opstack_for_l1` - Optional Optimism sequencer commitment validating the shared
+///   Ethereum block, required if any request validates L1 inclusion.
+/// * `env_input_opstack_for_l1_block_call` - Optional Optimism environment input for the shared Ethereum block,
+///   required if any request validates L1 inclusion.
+/// * `l1_hash_assumption` - Optional Ethereum block hash attested by an L1 hash proof; replaces the OpStack
+///   validation of the shared Ethereum block and is required to read the unsafe block signer from L1. See
+///   [`verify_l1_hash_assumption`].
+/// * `options` - Image IDs of the guests the requests may compose with, see [`ProofDataOptions`].
+/// * `output` - Output vector for proof data results.
+///
+/// # Panics
+/// Panics if:
+/// * No request is given or a source chain appears twice.
+/// * Requests validating L1 inclusion or reading their signer from L1 are on different networks.
+/// * An Ethereum environment for the L1 signer is given without an L1 hash assumption or not at its block.
+/// * Validation of the shared Ethereum block hash or of the L1 hash assumption fails, or an L1 hash assumption
+///   is given without an L1 hash image ID.
+/// * Any request fails [`validate_get_proof_data_call`].
+pub fn validate_get_proof_data_calls(
+    requests: Vec<ProofDataRequest>,
+    sequencer_commitment_opstack_for_l1: Option<SequencerCommitment>,
+    env_input_opstack_for_l1_block_call: Option<EthEvmInput>,
+    l1_hash_assumption: Option<L1HashAssumption>,
+    options: &ProofDataOptions,
+    output: &mut Vec<Bytes>,
+) {
+    assert!(!requests.is_empty(), "no proof data requests");
+    for (index, request) in requests.iter().enumerate() {
+        assert!(
+            requests[..index]
+                .iter()
+                .all(|other| other.chain_id != request.chain_id),
+            "duplicate proof data request for chain {}",
+            request.chain_id
+        );
+    }
+
+    // Validate the Ethereum block hash shared by all requests validating L1 inclusion.
+    let mut l1_networks = requests
+        .iter()
+        .filter(|request| {
+            request.env_input_eth_for_l1_inclusion.is_some()
+                || request.env_input_eth_for_l1_signer.is_some()
+        })
+        .map(|request| is_testnet_chain(request.chain_id, request.opstack_rollup_config.as_ref()));
+    let shared_ethereum_hash = l1_networks.next().map(|testnet| {
+        assert!(
+            l1_networks.all(|other| other == testnet),
+            "l1 inclusion requests on different networks"
+        );
+        let ethereum_chain_id = get_ethereum_chain_id(testnet);
+        match l1_hash_assumption.as_ref() {
+            Some(assumption) => verify_l1_hash_assumption(
+                options.l1_hash_image_id.expect("l1_hash_image_id is None"),
+                assumption,
+                ethereum_chain_id,
+            ),
+            None => get_validated_ethereum_block_hash_via_opstack(
+                sequencer_commitment_opstack_for_l1.as_ref(),
+                env_input_opstack_for_l1_block_call,
+                ethereum_chain_id,
+                None,
+                None,
+            ),
+        }
+    });
+
+    let assumed_ethereum_hash = l1_hash_assumption.and(shared_ethereum_hash);
+    for mut request in requests {
+        let validated_ethereum_hash = request
+            .env_input_eth_for_l1_inclusion
+            .as_ref()
+            .and(shared_ethereum_hash);
+        let eth_env_for_l1_signer = take_l1_signer_env(&mut request, assumed_ethereum_hash);
+        validate_proof_data_call(
+            request,
+            options,
             output,
+            validated_ethereum_hash,
+            eth_env_for_l1_signer.as_ref(),
         );
     }
 }
 
+/// Ethereum block hash attested by the journal of a prior L1 hash proof.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L1HashAssumption {
+    pub ethereum_chain_id: u64,
+    pub ethereum_hash: B256,
+}
+
+/// Encodes the journal of an L1 hash proof: `abi.encodePacked(uint64 ethereumChainId, bytes32 ethereumHash)`.
+///
+/// An L1 hash guest commits this after validating the hash. The assumption is what lets OpStack chains read their
+/// unsafe block signer from L1, so the guest must anchor the hash independently of OpStack sequencer keys, e.g.
+/// through the Ethereum beacon chain, and not with [`get_validated_ethereum_block_hash_via_opstack`].
+///
+/// # Arguments
+/// * `ethereum_chain_id` - The Ethereum chain ID (mainnet or Sepolia).
+/// * `ethereum_hash` - The validated Ethereum block hash.
+///
+/// # Returns
+/// * `Bytes` - The journal.
+pub fn encode_l1_hash_journal(ethereum_chain_id: u64, ethereum_hash: B256) -> Bytes {
+    let mut journal = Vec::with_capacity(40);
+    journal.extend_from_slice(&ethereum_chain_id.to_be_bytes());
+    journal.extend_from_slice(ethereum_hash.as_slice());
+    journal.into()
+}
+
+/// Returns the Ethereum block hash of an L1 hash assumption after verifying it through composition.
+///
+/// Adds an assumption that a receipt of the L1 hash guest with the given journal exists; the assumption is
+/// resolved when the proof is composed with that receipt. The image ID must be a constant compiled into the
+/// calling guest and never be read from its input, as otherwise any guest could vouch for the hash.
+///
+/// # Arguments
+/// * `image_id` - The image ID of the L1 hash guest.
+/// * `assumption` - The Ethereum block hash attested by the L1 hash proof.
+/// * `ethereum_chain_id` - The Ethereum chain ID the hash is expected for.
+///
+/// # Returns
+/// * `B256` - The Ethereum block hash.
+///
+/// # Panics
+/// Panics if:
+/// * The assumption is for a different Ethereum chain.
+/// * The assumption can't be added.
+/// * Called outside the zkVM guest.
+pub fn verify_l1_hash_assumption(
+    image_id: [u32; 8],
+    assumption: &L1HashAssumption,
+    ethereum_chain_id: u64,
+) -> B256 {
+    assert_eq!(
+        assumption.ethereum_chain_id, ethereum_chain_id,
+        "l1 hash assumption chain id mismatch"
+    );
+    let journal = encode_l1_hash_journal(assumption.ethereum_chain_id, assumption.ethereum_hash);
+    add_assumption(image_id, &journal);
+    assumption.ethereum_hash
+}
+
+/// Adds the assumption that a receipt of the given image with the given journal exists.
+#[cfg(target_os = "zkvm")]
+fn add_assumption(image_id: [u32; 8], journal: &[u8]) {
+    risc0_zkvm::guest::env::verify(image_id, journal).expect("Failed to verify assumption");
+}
+
+/// Assumptions only exist inside the zkVM guest.
+#[cfg(not(target_os = "zkvm"))]
+fn add_assumption(_image_id: [u32; 8], _journal: &[u8]) {
+    panic!("assumptions can only be verified in the guest");
+}
+
+/// Errors returned when the target chains of a proof data request are invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TargetChainError {
+    /// The account, asset and target chain vectors differ in length.
+    LengthMismatch {
+        accounts: usize,
+        assets: usize,
+        target_chain_ids: usize,
+    },
+    /// The target chain is not a supported chain.
+    UnsupportedTargetChain { index: usize, chain_id: u64 },
+    /// The target chain is the source chain.
+    TargetIsSource { index: usize, chain_id: u64 },
+    /// The target chain is on a different network (mainnet or testnet) than the source chain.
+    NetworkMismatch { index: usize, chain_id: u64 },
+    /// An extension chain targets a chain other than the host chain.
+    TargetNotHost {
+        index: usize,
+        chain_id: u64,
+        host: u64,
+    },
+}
+
+impl core::fmt::Display for TargetChainError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::LengthMismatch {
+                accounts,
+                assets,
+                target_chain_ids,
+            } => write!(
+                f,
+                "length mismatch: {accounts} accounts, {assets} assets, {target_chain_ids} target chain ids"
+            ),
+            Self::UnsupportedTargetChain { index, chain_id } => {
+                write!(f, "unsupported target chain {chain_id} at index {index}")
+            }
+            Self::TargetIsSource { index, chain_id } => {
+                write!(f, "target chain {chain_id} at index {index} is the source chain")
+            }
+            Self::NetworkMismatch { index, chain_id } => write!(
+                f,
+                "target chain {chain_id} at index {index} is on a different network"
+            ),
+            Self::TargetNotHost {
+                index,
+                chain_id,
+                host,
+            } => write!(
+                f,
+                "target chain {chain_id} at index {index} is not the host chain {host}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TargetChainError {}
+
+/// Returns whether the chain is a testnet.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID.
+/// * `opstack_rollup_config` - Optional rollup config of a configured chain.
+///
+/// # Panics
+/// Panics if the chain is neither built in nor configured.
+pub fn is_testnet_chain(
+    chain_id: u64,
+    opstack_rollup_config: Option<&OpStackRollupConfig>,
+) -> bool {
+    let l1_chain_id = match (chain_id, opstack_rollup_config) {
+        (_, Some(rollup_config)) => rollup_config.l1_chain_id,
+        (ETHEREUM_CHAIN_ID | ETHEREUM_SEPOLIA_CHAIN_ID, None) => chain_id,
+        _ => get_l1_chain_id(chain_id),
+    };
+    l1_chain_id == ETHEREUM_SEPOLIA_CHAIN_ID
+}
+
+/// Returns the Ethereum chain of the network.
+///
+/// # Arguments
+/// * `testnet` - Whether the network is the testnet.
+///
+/// # Returns
+/// * `u64` - The chain ID of Ethereum mainnet or Sepolia.
+pub fn get_ethereum_chain_id(testnet: bool) -> u64 {
+    if testnet {
+        ETHEREUM_SEPOLIA_CHAIN_ID
+    } else {
+        ETHEREUM_CHAIN_ID
+    }
+}
+
+/// Returns the host chain of the network the chain belongs to.
+///
+/// # Arguments
+/// * `testnet` - Whether the network is the testnet.
+///
+/// # Returns
+/// * `u64` - The chain ID of the host chain.
+pub fn get_host_chain_id(testnet: bool) -> u64 {
+    if testnet {
+        LINEA_SEPOLIA_CHAIN_ID
+    } else {
+        LINEA_CHAIN_ID
+    }
+}
+
+/// Checks the target chain IDs of a proof data request.
+///
+/// Every target must be a built-in or configured chain of the same network as the source and differ from it.
+/// Proofs from extension chains are only accepted by the host chain, so extension chains must target the host
+/// chain.
+///
+/// Target rollup configs are supplied by the prover and not committed to the journal, so this check only rejects
+/// malformed requests early. It doesn't protect the destination: an entry is only accepted by the market on its
+/// target chain, which checks the target chain ID against its own and the source chain against its allowed chains.
+///
+/// # Arguments
+/// * `chain_id` - The source chain ID.
+/// * `opstack_rollup_config` - Optional rollup config of a configured source chain.
+/// * `target_opstack_rollup_configs` - Rollup configs of the configured chains that may be targeted.
+/// * `accounts` - The number of accounts in the request.
+/// * `assets` - The number of assets in the request.
+/// * `target_chain_ids` - The target chain ID of each entry.
+///
+/// # Returns
+/// * `Result<(), TargetChainError>` - `Ok` if every target chain is valid.
+pub fn check_target_chain_ids(
+    chain_id: u64,
+    opstack_rollup_config: Option<&OpStackRollupConfig>,
+    target_opstack_rollup_configs: &[OpStackRollupConfig],
+    accounts: usize,
+    assets: usize,
+    target_chain_ids: &[u64],
+) -> Result<(), TargetChainError> {
+    if accounts != assets || accounts != target_chain_ids.len() {
+        return Err(TargetChainError::LengthMismatch {
+            accounts,
+            assets,
+            target_chain_ids: target_chain_ids.len(),
+        });
+    }
+
+    let testnet = is_testnet_chain(chain_id, opstack_rollup_config);
+    let host = get_host_chain_id(testnet);
+    for (index, &target) in target_chain_ids.iter().enumerate() {
+        let target_rollup_config = target_opstack_rollup_configs
+            .iter()
+            .find(|rollup_config| rollup_config.l2_chain_id == target);
+        if !is_builtin_chain(target) && target_rollup_config.is_none() {
+            return Err(TargetChainError::UnsupportedTargetChain {
+                index,
+                chain_id: target,
+            });
+        }
+        if target == chain_id {
+            return Err(TargetChainError::TargetIsSource {
+                index,
+                chain_id: target,
+            });
+        }
+        if is_testnet_chain(target, target_rollup_config) != testnet {
+            return Err(TargetChainError::NetworkMismatch {
+                index,
+                chain_id: target,
+            });
+        }
+        if chain_id != host && target != host {
+            return Err(TargetChainError::TargetNotHost {
+                index,
+                chain_id: target,
+                host,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Condition under which a proof data entry may only be proven with L1 inclusion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum L1InclusionRule {
+    /// The entry is for the market and its `amountOut` exceeds the threshold.
+    AmountOutAbove { market: Address, threshold: U256 },
+    /// The entry targets the chain.
+    TargetChain { chain_id: u64 },
+}
+
+impl L1InclusionRule {
+    /// Returns whether the rule requires L1 inclusion for the given entry.
+    pub fn applies(&self, market: Address, target_chain_id: u64, amount_out: U256) -> bool {
+        match *self {
+            Self::AmountOutAbove {
+                market: rule_market,
+                threshold,
+            } => market == rule_market && amount_out > threshold,
+            Self::TargetChain { chain_id } => target_chain_id == chain_id,
+        }
+    }
+}
+
+/// Errors returned when the L1 inclusion policy is not satisfied.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum L1InclusionPolicyError {
+    /// A rule requires L1 inclusion for an entry proven without it.
+    InclusionRequired { index: usize, rule: L1InclusionRule },
+}
+
+impl core::fmt::Display for L1InclusionPolicyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InclusionRequired { index, rule } => {
+                write!(f, "l1 inclusion required for entry {index} by {rule:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for L1InclusionPolicyError {}
+
+/// Rules of the Linea host chain: proofs releasing funds on Ethereum mainnet must be validated through L1.
+const LINEA_L1_INCLUSION_RULES: &[L1InclusionRule] = &[L1InclusionRule::TargetChain {
+    chain_id: ETHEREUM_CHAIN_ID,
+}];
+/// Rules of the Linea Sepolia host chain, mirroring mainnet.
+const LINEA_SEPOLIA_L1_INCLUSION_RULES: &[L1InclusionRule] = &[L1InclusionRule::TargetChain {
+    chain_id: ETHEREUM_SEPOLIA_CHAIN_ID,
+}];
+
+/// Returns the rules requiring L1 inclusion for proofs on the given chain.
+///
+/// Extension chains only target the host chain, so their proofs are not subject to target chain rules.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID the proof is generated for.
+///
+/// # Returns
+/// * `&'static [L1InclusionRule]` - The rules; L1 inclusion is required if any rule applies to any entry.
+///
+/// # Panics
+/// Panics if the chain ID is invalid or unsupported.
+pub fn get_l1_inclusion_rules(chain_id: u64) -> &'static [L1InclusionRule] {
+    match chain_id {
+        ETHEREUM_CHAIN_ID
+        | ETHEREUM_SEPOLIA_CHAIN_ID
+        | OPTIMISM_CHAIN_ID
+        | OPTIMISM_SEPOLIA_CHAIN_ID
+        | BASE_CHAIN_ID
+        | BASE_SEPOLIA_CHAIN_ID
+        | SCROLL_CHAIN_ID
+        | SCROLL_SEPOLIA_CHAIN_ID => &[],
+        LINEA_CHAIN_ID => LINEA_L1_INCLUSION_RULES,
+        LINEA_SEPOLIA_CHAIN_ID => LINEA_SEPOLIA_L1_INCLUSION_RULES,
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Checks the proof data entries against the L1 inclusion rules of a chain.
+///
+/// # Arguments
+/// * `rules` - The L1 inclusion rules of the chain.
+/// * `validate_l1_inclusion` - Whether L1 inclusion was validated; if so, every rule is satisfied.
+/// * `markets` - The market of each entry.
+/// * `target_chain_ids` - The target chain ID of each entry.
+/// * `amounts` - The `(amountIn, amountOut)` returned for each entry.
+///
+/// # Returns
+/// * `Result<(), L1InclusionPolicyError>` - `Ok` if no rule requires missing L1 inclusion.
+pub fn check_l1_inclusion_policy(
+    rules: &[L1InclusionRule],
+    validate_l1_inclusion: bool,
+    markets: &[Address],
+    target_chain_ids: &[u64],
+    amounts: &[(U256, U256)],
+) -> Result<(), L1InclusionPolicyError> {
+    if validate_l1_inclusion {
+        return Ok(());
+    }
+    let entries = markets.iter().zip(target_chain_ids).zip(amounts);
+    for (index, ((market, target_chain_id), (_amount_in, amount_out))) in entries.enumerate() {
+        if let Some(rule) = rules
+            .iter()
+            .find(|rule| rule.applies(*market, *target_chain_id, *amount_out))
+        {
+            return Err(L1InclusionPolicyError::InclusionRequired { index, rule: *rule });
+        }
+    }
+    Ok(())
+}
+
 /// Sorts and verifies relevant parameters for proof data validation.
 ///
 /// This function processes and validates input parameters for different chain types,
@@ -159,6 +983,7 @@ pub fn validate_get_proof_data_call(
 /// * `linking_blocks` - Vector of blocks for reorg protection.
 /// * `env_input_eth_for_l1_inclusion` - Optional Ethereum input for L1 inclusion (used for OpStack/Linea L2s).
 /// * `env_input_opstack_for_viewcall_with_l1_inclusion` - Optional OpStack input for L1 inclusion (used for OpStack L2s).
+/// * `opstack_rollup_config` - Optional rollup config of a configured OpStack chain.
 ///
 /// # Returns
 /// Returns a tuple containing:
@@ -176,12 +1001,14 @@ pub fn validate_get_proof_data_call(
 /// * Chain ID is invalid.
 /// * Required environment inputs are missing.
 /// * Parameter validation fails.
+#[allow(clippy::type_complexity)]
 pub fn sort_and_verify_relevant_params(
     chain_id: u64,
     env_input_for_viewcall: Option<EthEvmInput>,
-    linking_blocks: &Vec<RlpHeader<Header>>,
+    linking_blocks: &[RlpHeader<Header>],
     env_input_eth_for_l1_inclusion: &Option<EthEvmInput>,
     env_input_opstack_for_viewcall_with_l1_inclusion: Option<OpEvmInput>,
+    opstack_rollup_config: Option<&OpStackRollupConfig>,
 ) -> (
     EvmEnv<StateDb, EthEvmFactory, Commitment>,
     RlpHeader<Header>,
@@ -203,25 +1030,23 @@ pub fn sort_and_verify_relevant_params(
     ) = if (chain_id == OPTIMISM_CHAIN_ID
         || chain_id == BASE_CHAIN_ID
         || chain_id == OPTIMISM_SEPOLIA_CHAIN_ID
-        || chain_id == BASE_SEPOLIA_CHAIN_ID)
+        || chain_id == BASE_SEPOLIA_CHAIN_ID
+        || opstack_rollup_config.is_some())
         && validate_l1_inclusion
     {
         // For OpStack L2s with L1 inclusion, use the L1 environment and OpStack environment for inclusion.
+        let chain_id_for_length_validation =
+            get_opstack_l1_chain_id(chain_id, opstack_rollup_config);
         let env_for_viewcall = env_input_eth_for_l1_inclusion
             .as_ref()
             .expect("env_eth_input is None")
             .clone()
-            .into_env(&ETH_MAINNET_CHAIN_SPEC);
+            .into_env(get_eth_chain_spec(chain_id_for_length_validation));
         let op_env_for_viewcall_with_l1_inclusion =
             env_input_opstack_for_viewcall_with_l1_inclusion
                 .expect("op_evm_input is None")
-                .into_env(&OP_MAINNET_CHAIN_SPEC);
+                .into_env(get_op_chain_spec(chain_id, opstack_rollup_config));
         let op_env_commitment = op_env_for_viewcall_with_l1_inclusion.commitment().clone();
-        let chain_id_for_length_validation = match chain_id {
-            OPTIMISM_CHAIN_ID | BASE_CHAIN_ID => ETHEREUM_CHAIN_ID,
-            OPTIMISM_SEPOLIA_CHAIN_ID | BASE_SEPOLIA_CHAIN_ID => ETHEREUM_SEPOLIA_CHAIN_ID,
-            _ => panic!("invalid chain id"),
-        };
         (
             env_for_viewcall,
             Some(op_env_for_viewcall_with_l1_inclusion),
@@ -229,17 +1054,17 @@ pub fn sort_and_verify_relevant_params(
             chain_id_for_length_validation,
         )
     } else {
-        // For L1 or Linea chains, use the provided environment input.
-        let chain_spec = match chain_id {
-            LINEA_CHAIN_ID => &LINEA_MAINNET_CHAIN_SPEC,
-            LINEA_SEPOLIA_CHAIN_ID => &LINEA_MAINNET_CHAIN_SPEC,
-            _ => &ETH_MAINNET_CHAIN_SPEC,
+        // For L1, Linea or Scroll chains, use the provided environment input. Configured OpStack
+        // chains use the spec of the network they settle on.
+        let chain_spec = match opstack_rollup_config {
+            Some(rollup_config) => get_eth_chain_spec(rollup_config.l1_chain_id),
+            None => get_eth_chain_spec(chain_id),
         };
 
         (
             env_input_for_viewcall
                 .expect("env_input is None")
-                .into_env(&chain_spec),
+                .into_env(chain_spec),
             None,
             None,
             chain_id,
@@ -253,7 +1078,15 @@ pub fn sort_and_verify_relevant_params(
         linking_blocks[linking_blocks.len() - 1].clone()
     };
 
-    let env_header_hash_to_validate = env_for_viewcall.header().seal();
+    // Linea QBFT blocks are identified by a hash that excludes the committed seals, so for Linea the hash
+    // to link from is computed from the header rather than taken from the environment's header seal.
+    let env_header_hash_to_validate = match chain_id_for_length_validation {
+        LINEA_CHAIN_ID | LINEA_SEPOLIA_CHAIN_ID => get_block_hash(
+            chain_id_for_length_validation,
+            env_for_viewcall.header().inner().inner(),
+        ),
+        _ => env_for_viewcall.header().seal(),
+    };
     let env_header_to_validate = env_for_viewcall.header().inner().inner().clone();
 
     (
@@ -268,46 +1101,334 @@ pub fn sort_and_verify_relevant_params(
     )
 }
 
-/// Validates an OpStack dispute game commitment.
-///
-/// This function verifies the dispute game state and commitment for OpStack chains,
-/// ensuring the game is valid and properly resolved. It checks the game type, creation time,
-/// status, blacklist status, resolution time, and root claim.
-///
-/// # Arguments
-/// * `chain_id` - The OpStack chain ID.
-/// * `eth_env` - The Ethereum EVM environment.
-/// * `op_env_commitment` - The OpStack commitment to validate.
-///
-/// # Panics
-/// Panics if:
-/// * Chain ID is invalid.
-/// * Game type is not respected.
-/// * Game was created before respected game type update.
-/// * Game status is not DEFENDER_WINS.
-/// * Game is blacklisted.
-/// * Insufficient time has passed since game resolution.
-/// * Root claim doesn't match.
-pub fn validate_opstack_dispute_game_commitment(
+/// Canonical fault dispute game (permissionless Cannon).
+pub const GAME_TYPE_CANNON: u32 = 0;
+/// Permissioned Cannon dispute game, where only the proposer and challenger may participate.
+pub const GAME_TYPE_PERMISSIONED_CANNON: u32 = 1;
+/// Asterisc (RISC-V) fault dispute game.
+pub const GAME_TYPE_ASTERISC: u32 = 2;
+/// Asterisc fault dispute game running the Kona fault proof program.
+pub const GAME_TYPE_ASTERISC_KONA: u32 = 3;
+/// Super root (interop) Cannon dispute game.
+pub const GAME_TYPE_SUPER_CANNON: u32 = 4;
+/// Super root (interop) permissioned Cannon dispute game.
+pub const GAME_TYPE_SUPER_PERMISSIONED_CANNON: u32 = 5;
+/// OP Succinct validity-proof dispute game.
+pub const GAME_TYPE_OP_SUCCINCT: u32 = 6;
+
+/// Returns whether games of the given type attest to a super root rather than an output root.
+pub fn is_super_root_game_type(game_type: u32) -> bool {
+    matches!(game_type, GAME_TYPE_SUPER_CANNON | GAME_TYPE_SUPER_PERMISSIONED_CANNON)
+}
+
+sol! {
+    /// OptimismPortal getters not covered by `IOptimismPortal`.
+    interface IOptimismPortalGameType {
+        function respectedGameType() external view returns (uint32);
+    }
+
+    /// Dispute game getters not covered by `IDisputeGame`.
+    interface IFaultDisputeGame {
+        function l2BlockNumber() external view returns (uint256);
+        function extraData() external view returns (bytes memory);
+        function wasRespectedGameTypeWhenCreated() external view returns (bool);
+    }
+}
+
+/// Default slack subtracted from the portal's `proofMaturityDelaySeconds`.
+pub const DEFAULT_PROOF_MATURITY_MARGIN_SECONDS: u64 = 300;
+
+/// Dispute game requirements for an OpStack chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisputeGamePolicy {
+    /// Game types accepted for this chain. The game must additionally be of the portal's respected type.
+    pub accepted_game_types: &'static [u32],
+    /// Seconds subtracted from `proofMaturityDelaySeconds` when checking the game resolution age.
+    pub proof_maturity_margin_seconds: u64,
+    /// Demands the full `proofMaturityDelaySeconds`, as the portal does for withdrawals, ignoring the margin.
+    pub strict_proof_maturity: bool,
+    /// Requires `wasRespectedGameTypeWhenCreated`, which games created before the flag existed don't expose.
+    pub require_respected_when_created: bool,
+}
+
+/// Errors returned when a dispute game fails the game type or proof maturity checks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisputeGameError {
+    /// The portal's respected game type is not accepted for the chain.
+    RespectedGameTypeNotAccepted { respected: u32 },
+    /// The game is not of the portal's respected game type.
+    GameTypeNotRespected { game_type: U256, respected: u32 },
+    /// The game resolved after the timestamp of the Ethereum block used for validation.
+    ResolvedInFuture { resolved_at: u64, current: u64 },
+    /// The portal's proof maturity delay does not fit in a `u64`.
+    MaturityDelayOutOfRange { delay: U256 },
+    /// The maturity margin is larger than the portal's proof maturity delay.
+    MarginExceedsDelay { delay: u64, margin: u64 },
+    /// Not enough time has passed since the game resolved.
+    InsufficientMaturity { elapsed: u64, required: u64 },
+}
+
+impl core::fmt::Display for DisputeGameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::RespectedGameTypeNotAccepted { respected } => {
+                write!(f, "respected game type not accepted: {respected}")
+            }
+            Self::GameTypeNotRespected {
+                game_type,
+                respected,
+            } => write!(
+                f,
+                "game type not respected game: {game_type} != {respected}"
+            ),
+            Self::ResolvedInFuture {
+                resolved_at,
+                current,
+            } => write!(f, "game resolved in the future: {resolved_at} > {current}"),
+            Self::MaturityDelayOutOfRange { delay } => {
+                write!(f, "proof maturity delay out of range: {delay}")
+            }
+            Self::MarginExceedsDelay { delay, margin } => {
+                write!(f, "proof maturity margin exceeds delay: {margin} > {delay}")
+            }
+            Self::InsufficientMaturity { elapsed, required } => write!(
+                f,
+                "insufficient time passed since game resolution: {elapsed}s <= {required}s"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DisputeGameError {}
+
+/// Checks that the portal's respected game type is accepted by a dispute game policy and that the game is of it.
+///
+/// # Arguments
+/// * `policy` - The dispute game policy of the chain.
+/// * `respected_game_type` - The portal's `respectedGameType`.
+/// * `game_type` - The type of the dispute game.
+///
+/// # Returns
+/// * `Result<(), DisputeGameError>` - `Ok` if the game type is accepted and respected.
+pub fn check_game_type(
+    policy: &DisputeGamePolicy,
+    respected_game_type: u32,
+    game_type: U256,
+) -> Result<(), DisputeGameError> {
+    if !policy.accepted_game_types.contains(&respected_game_type) {
+        return Err(DisputeGameError::RespectedGameTypeNotAccepted {
+            respected: respected_game_type,
+        });
+    }
+    if game_type != U256::from(respected_game_type) {
+        return Err(DisputeGameError::GameTypeNotRespected {
+            game_type,
+            respected: respected_game_type,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that a dispute game resolved long enough ago according to a dispute game policy.
+///
+/// # Arguments
+/// * `policy` - The dispute game policy of the chain.
+/// * `current_timestamp` - The timestamp of the Ethereum block used for validation.
+/// * `resolved_at` - The timestamp at which the game resolved.
+/// * `proof_maturity_delay` - The portal's `proofMaturityDelaySeconds`.
+///
+/// # Returns
+/// * `Result<(), DisputeGameError>` - `Ok` if the game is mature.
+pub fn check_proof_maturity(
+    policy: &DisputeGamePolicy,
+    current_timestamp: u64,
+    resolved_at: u64,
+    proof_maturity_delay: U256,
+) -> Result<(), DisputeGameError> {
+    let elapsed = current_timestamp
+        .checked_sub(resolved_at)
+        .ok_or(DisputeGameError::ResolvedInFuture {
+            resolved_at,
+            current: current_timestamp,
+        })?;
+    let delay = u64::try_from(proof_maturity_delay).map_err(|_| {
+        DisputeGameError::MaturityDelayOutOfRange {
+            delay: proof_maturity_delay,
+        }
+    })?;
+    let required = if policy.strict_proof_maturity {
+        delay
+    } else {
+        delay
+            .checked_sub(policy.proof_maturity_margin_seconds)
+            .ok_or(DisputeGameError::MarginExceedsDelay {
+                delay,
+                margin: policy.proof_maturity_margin_seconds,
+            })?
+    };
+    // Mirror the portal, which requires strictly more than the delay to have passed.
+    if elapsed <= required {
+        return Err(DisputeGameError::InsufficientMaturity { elapsed, required });
+    }
+    Ok(())
+}
+
+/// Returns the dispute game policy for the given OpStack chain.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+///
+/// # Returns
+/// * `DisputeGamePolicy` - The dispute game requirements for the chain.
+///
+/// # Panics
+/// Panics if the chain ID is invalid or unsupported.
+pub fn get_dispute_game_policy(chain_id: u64) -> DisputeGamePolicy {
+    match chain_id {
+        OPTIMISM_CHAIN_ID | BASE_CHAIN_ID => DisputeGamePolicy {
+            accepted_game_types: &[
+                GAME_TYPE_CANNON,
+                GAME_TYPE_PERMISSIONED_CANNON,
+                GAME_TYPE_SUPER_CANNON,
+                GAME_TYPE_SUPER_PERMISSIONED_CANNON,
+            ],
+            proof_maturity_margin_seconds: DEFAULT_PROOF_MATURITY_MARGIN_SECONDS,
+            strict_proof_maturity: false,
+            require_respected_when_created: true,
+        },
+        OPTIMISM_SEPOLIA_CHAIN_ID | BASE_SEPOLIA_CHAIN_ID => DisputeGamePolicy {
+            accepted_game_types: &[
+                GAME_TYPE_CANNON,
+                GAME_TYPE_PERMISSIONED_CANNON,
+                GAME_TYPE_ASTERISC,
+                GAME_TYPE_ASTERISC_KONA,
+                GAME_TYPE_SUPER_CANNON,
+                GAME_TYPE_SUPER_PERMISSIONED_CANNON,
+                GAME_TYPE_OP_SUCCINCT,
+            ],
+            proof_maturity_margin_seconds: DEFAULT_PROOF_MATURITY_MARGIN_SECONDS,
+            strict_proof_maturity: false,
+            require_respected_when_created: true,
+        },
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Validates an OpStack dispute game commitment.
+///
+/// This function verifies the dispute game state and commitment for OpStack chains,
+/// ensuring the game is valid and properly resolved. It checks the game type against the portal's
+/// respected game type and the chain's accepted game types, creation time, the L2 block number the
+/// game is about, status, blacklist status, resolution time, and root claim. The expected root claim
+/// is recomputed from the validated L2 header and the L2ToL1MessagePasser account proof, so it is
+/// explicit which L2 block the dispute game attests to.
+///
+/// For super root (interop) games, the root claim is the super root of the dependency set: the super
+/// root is verified against the game and the chain's output root is proven to be part of it.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+/// * `eth_env` - The Ethereum EVM environment.
+/// * `op_env_commitment` - The OpStack commitment to validate.
+/// * `l2_header` - The validated L2 header of the OpStack environment.
+/// * `message_passer_proof` - The L2ToL1MessagePasser account proof against the L2 state root.
+/// * `super_root_proof` - The super root preimage, required for super root games.
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is invalid.
+/// * The game type fails [`check_game_type`].
+/// * Game was created before respected game type update.
+/// * Game was not of the respected game type when created.
+/// * Game L2 block number or extra data doesn't match the L2 header (or super root timestamp).
+/// * A super root game is validated without a valid super root proof for the chain.
+/// * Game status is not DEFENDER_WINS.
+/// * Game is blacklisted.
+/// * The game resolution age fails [`check_proof_maturity`].
+/// * The message passer account proof is invalid.
+/// * Root claim doesn't match the recomputed output root.
+pub fn validate_opstack_dispute_game_commitment(
     chain_id: u64,
     eth_env: EvmEnv<StateDb, EthEvmFactory, Commitment>,
     op_env_commitment: &Commitment,
+    l2_header: &Header,
+    message_passer_proof: &MessagePasserAccountProof,
+    super_root_proof: Option<&SuperRootProof>,
 ) {
-    // Decode the game index and root claim from the commitment.
-    let (game_index, _version) = op_env_commitment.decode_id();
-    let root_claim = op_env_commitment.digest;
+    validate_opstack_dispute_game_commitment_with_portal(
+        chain_id,
+        get_opstack_portal(chain_id),
+        &get_dispute_game_policy(chain_id),
+        eth_env,
+        op_env_commitment,
+        l2_header,
+        message_passer_proof,
+        super_root_proof,
+    )
+}
 
-    // Select the correct portal address for the given chain.
-    let portal_adress = match chain_id {
+/// Returns the OptimismPortal of the given built-in OpStack chain.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+///
+/// # Returns
+/// * `Address` - The portal address on L1.
+///
+/// # Panics
+/// Panics if the chain ID is invalid or unsupported.
+pub fn get_opstack_portal(chain_id: u64) -> Address {
+    match chain_id {
         OPTIMISM_SEPOLIA_CHAIN_ID => OPTIMISM_SEPOLIA_PORTAL,
         BASE_SEPOLIA_CHAIN_ID => BASE_SEPOLIA_PORTAL,
         OPTIMISM_CHAIN_ID => OPTIMISM_PORTAL,
         BASE_CHAIN_ID => BASE_PORTAL,
         _ => panic!("invalid chain id"),
-    };
+    }
+}
+
+/// Validates an OpStack dispute game commitment against the given portal and dispute game policy.
+///
+/// Performs the checks of [`validate_opstack_dispute_game_commitment`] for chains whose portal and policy are
+/// not built in, such as chains onboarded from an [`OpStackRollupConfig`].
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+/// * `portal_address` - The OptimismPortal of the chain on L1.
+/// * `policy` - The dispute game policy of the chain.
+/// * `eth_env` - The Ethereum EVM environment.
+/// * `op_env_commitment` - The OpStack environment commitment.
+/// * `l2_header` - The OpStack L2 block header the commitment refers to.
+/// * `message_passer_proof` - The L2ToL1MessagePasser account proof against the L2 state root.
+/// * `super_root_proof` - Optional super root preimage, required for super root games.
+///
+/// # Panics
+/// Panics under the same conditions as [`validate_opstack_dispute_game_commitment`].
+#[allow(clippy::too_many_arguments)]
+pub fn validate_opstack_dispute_game_commitment_with_portal(
+    chain_id: u64,
+    portal_address: Address,
+    policy: &DisputeGamePolicy,
+    eth_env: EvmEnv<StateDb, EthEvmFactory, Commitment>,
+    op_env_commitment: &Commitment,
+    l2_header: &Header,
+    message_passer_proof: &MessagePasserAccountProof,
+    super_root_proof: Option<&SuperRootProof>,
+) {
+    // Decode the game index from the commitment.
+    let (game_index, _version) = op_env_commitment.decode_id();
+
+    // Recompute the output root of the L2 block and make sure the commitment refers to it.
+    let message_passer_storage_root =
+        verify_message_passer_account_proof(l2_header.state_root, message_passer_proof);
+    let output_root = compute_opstack_output_root(l2_header, message_passer_storage_root);
+    assert_eq!(
+        op_env_commitment.digest, output_root,
+        "output root mismatch commitment"
+    );
 
     // Get the portal contract for additional checks.
-    let portal_contract = Contract::new(portal_adress, &eth_env);
+    let portal_contract = Contract::new(portal_address, &eth_env);
 
     // Get factory address from portal.
     let factory_call = IOptimismPortal::disputeGameFactoryCall {};
@@ -323,8 +1444,14 @@ pub fn validate_opstack_dispute_game_commitment(
     let created_at = returns._1;
     let game_address = returns._2;
 
-    // Ensure the game type is respected (must be 0).
-    assert_eq!(game_type, U256::from(0), "game type not respected game");
+    // Ensure the portal's respected game type is accepted for this chain and the game is of that type.
+    let respected_game_type_call = IOptimismPortalGameType::respectedGameTypeCall {};
+    let respected_game_type = portal_contract
+        .call_builder(&respected_game_type_call)
+        .call();
+    if let Err(err) = check_game_type(policy, respected_game_type, game_type) {
+        panic!("{err}");
+    }
 
     // Check if game was created after respected game type update.
     let respected_game_type_updated_at_call = IOptimismPortal::respectedGameTypeUpdatedAtCall {};
@@ -339,6 +1466,38 @@ pub fn validate_opstack_dispute_game_commitment(
     // Get game contract for status checks.
     let game_contract = Contract::new(game_address, &eth_env);
 
+    // Check the game was of the respected type when it was created.
+    if policy.require_respected_when_created {
+        let was_respected_call = IFaultDisputeGame::wasRespectedGameTypeWhenCreatedCall {};
+        let was_respected = game_contract.call_builder(&was_respected_call).call();
+        assert!(was_respected, "game type not respected when game was created");
+    }
+
+    // Determine the expected root claim and the L2 sequence number the game must be about: the L2
+    // block number for output root games, the super root timestamp for super root games.
+    let (root_claim, l2_sequence_number) = if is_super_root_game_type(respected_game_type) {
+        let super_root_proof = super_root_proof.expect("super_root_proof is None");
+        let super_root =
+            validate_super_root_proof(chain_id, l2_header, output_root, super_root_proof);
+        (super_root, U256::from(super_root_proof.timestamp))
+    } else {
+        // Check the game is about the L2 block of the OpStack environment.
+        let l2_block_number_call = IFaultDisputeGame::l2BlockNumberCall {};
+        let l2_block_number = game_contract.call_builder(&l2_block_number_call).call();
+        assert_eq!(
+            l2_block_number,
+            U256::from(l2_header.number),
+            "game l2 block number mismatch"
+        );
+        (output_root, U256::from(l2_header.number))
+    };
+    let extra_data_call = IFaultDisputeGame::extraDataCall {};
+    let extra_data = game_contract.call_builder(&extra_data_call).call();
+    assert!(
+        extra_data.len() >= 32 && U256::from_be_slice(&extra_data[..32]) == l2_sequence_number,
+        "game extra data mismatch"
+    );
+
     // Check game status.
     let status_call = IDisputeGame::statusCall {};
     let status = game_contract.call_builder(&status_call).call();
@@ -363,11 +1522,14 @@ pub fn validate_opstack_dispute_game_commitment(
         .call();
 
     let current_timestamp = eth_env.header().inner().inner().timestamp;
-    assert!(
-        U256::from(current_timestamp) - U256::from(resolved_at)
-            > proof_maturity_delay - U256::from(300),
-        "insufficient time passed since game resolution"
-    );
+    if let Err(err) = check_proof_maturity(
+        policy,
+        current_timestamp,
+        resolved_at,
+        proof_maturity_delay,
+    ) {
+        panic!("{err}");
+    }
 
     // Finally verify root claim matches.
     let root_claim_call = IDisputeGame::rootClaimCall {};
@@ -375,6 +1537,242 @@ pub fn validate_opstack_dispute_game_commitment(
     assert_eq!(root_claim_return, root_claim, "root claim mismatch");
 }
 
+/// Address of the L2ToL1MessagePasser predeploy on OpStack chains.
+pub const L2_TO_L1_MESSAGE_PASSER_OPSTACK: Address =
+    address!("4200000000000000000000000000000000000016");
+
+/// Version of the OpStack output root format.
+pub const OPSTACK_OUTPUT_ROOT_VERSION: B256 = B256::ZERO;
+
+/// Account proof of the L2ToL1MessagePasser predeploy on an OpStack chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MessagePasserAccountProof {
+    pub nonce: u64,
+    pub balance: U256,
+    pub storage_root: B256,
+    pub code_hash: B256,
+    /// RLP-encoded trie nodes from the state root to the account leaf.
+    pub proof: Vec<Bytes>,
+}
+
+/// Verifies the L2ToL1MessagePasser account proof against an L2 state root.
+///
+/// # Arguments
+/// * `state_root` - The state root of the validated L2 header.
+/// * `account_proof` - The account proof of the message passer.
+///
+/// # Returns
+/// * `B256` - The storage root of the message passer.
+///
+/// # Panics
+/// Panics if the account proof is invalid.
+pub fn verify_message_passer_account_proof(
+    state_root: B256,
+    account_proof: &MessagePasserAccountProof,
+) -> B256 {
+    let account = TrieAccount {
+        nonce: account_proof.nonce,
+        balance: account_proof.balance,
+        storage_root: account_proof.storage_root,
+        code_hash: account_proof.code_hash,
+    };
+    verify_proof(
+        state_root,
+        Nibbles::unpack(keccak256(L2_TO_L1_MESSAGE_PASSER_OPSTACK)),
+        Some(alloy_rlp::encode(account)),
+        &account_proof.proof,
+    )
+    .expect("invalid message passer account proof");
+    account_proof.storage_root
+}
+
+/// Computes the OpStack output root of an L2 block.
+///
+/// The output root is `keccak256(version ++ state_root ++ message_passer_storage_root ++ block_hash)`,
+/// which is the value dispute games propose as their root claim.
+///
+/// # Arguments
+/// * `l2_header` - The L2 block header.
+/// * `message_passer_storage_root` - The storage root of the L2ToL1MessagePasser at that block.
+///
+/// # Returns
+/// * `B256` - The output root.
+pub fn compute_opstack_output_root(l2_header: &Header, message_passer_storage_root: B256) -> B256 {
+    opstack_output_root(
+        l2_header.state_root,
+        message_passer_storage_root,
+        l2_header.hash_slow(),
+    )
+}
+
+/// Computes a v0 OpStack output root from its components.
+///
+/// # Arguments
+/// * `state_root` - The L2 state root.
+/// * `message_passer_storage_root` - The storage root of the L2ToL1MessagePasser.
+/// * `block_hash` - The L2 block hash.
+///
+/// # Returns
+/// * `B256` - The output root.
+pub fn opstack_output_root(
+    state_root: B256,
+    message_passer_storage_root: B256,
+    block_hash: B256,
+) -> B256 {
+    let mut preimage = [0u8; 128];
+    preimage[0..32].copy_from_slice(OPSTACK_OUTPUT_ROOT_VERSION.as_slice());
+    preimage[32..64].copy_from_slice(state_root.as_slice());
+    preimage[64..96].copy_from_slice(message_passer_storage_root.as_slice());
+    preimage[96..128].copy_from_slice(block_hash.as_slice());
+    keccak256(preimage)
+}
+
+/// Version byte of the v1 super root encoding.
+pub const SUPER_ROOT_VERSION_V1: u8 = 1;
+
+/// Output root of a single chain inside a super root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuperRootOutput {
+    pub chain_id: u64,
+    pub output_root: B256,
+}
+
+/// Preimage of an OpStack super root, covering the output roots of all chains in a dependency set.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuperRootProof {
+    pub version: u8,
+    pub timestamp: u64,
+    /// Output roots ordered by chain ID, as in the super root encoding.
+    pub output_roots: Vec<SuperRootOutput>,
+}
+
+impl SuperRootProof {
+    /// Computes the super root: `keccak256(version ++ timestamp ++ (chain_id ++ output_root)*)`.
+    pub fn super_root(&self) -> B256 {
+        let mut preimage = Vec::with_capacity(9 + self.output_roots.len() * 64);
+        preimage.push(self.version);
+        preimage.extend_from_slice(&self.timestamp.to_be_bytes());
+        for output in &self.output_roots {
+            preimage.extend_from_slice(&U256::from(output.chain_id).to_be_bytes::<32>());
+            preimage.extend_from_slice(output.output_root.as_slice());
+        }
+        keccak256(preimage)
+    }
+
+    /// Returns the output root of the given chain, if the chain is part of the super root.
+    pub fn output_root(&self, chain_id: u64) -> Option<B256> {
+        self.output_roots
+            .iter()
+            .find(|output| output.chain_id == chain_id)
+            .map(|output| output.output_root)
+    }
+}
+
+/// Errors returned when an output root can't be tied to a super root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SuperRootError {
+    /// The super root uses an encoding version other than v1.
+    UnsupportedVersion { version: u8 },
+    /// The L2 block is newer than the super root timestamp.
+    BlockAfterTimestamp {
+        block_timestamp: u64,
+        timestamp: u64,
+    },
+    /// The chain is not part of the super root.
+    ChainNotIncluded { chain_id: u64 },
+    /// The chain's output root in the super root differs from the recomputed one.
+    OutputRootMismatch { expected: B256, found: B256 },
+}
+
+impl core::fmt::Display for SuperRootError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnsupportedVersion { version } => {
+                write!(f, "unsupported super root version {version}")
+            }
+            Self::BlockAfterTimestamp {
+                block_timestamp,
+                timestamp,
+            } => write!(
+                f,
+                "l2 block newer than super root timestamp: {block_timestamp} > {timestamp}"
+            ),
+            Self::ChainNotIncluded { chain_id } => {
+                write!(f, "chain {chain_id} not part of super root")
+            }
+            Self::OutputRootMismatch { expected, found } => {
+                write!(f, "output root mismatch super root: {found} != {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SuperRootError {}
+
+/// Checks that a chain's output root is part of a super root.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+/// * `l2_header` - The validated L2 header of the OpStack environment.
+/// * `output_root` - The output root recomputed from the L2 header.
+/// * `super_root_proof` - The super root preimage.
+///
+/// # Returns
+/// * `Result<B256, SuperRootError>` - The super root.
+pub fn check_super_root_proof(
+    chain_id: u64,
+    l2_header: &Header,
+    output_root: B256,
+    super_root_proof: &SuperRootProof,
+) -> Result<B256, SuperRootError> {
+    if super_root_proof.version != SUPER_ROOT_VERSION_V1 {
+        return Err(SuperRootError::UnsupportedVersion {
+            version: super_root_proof.version,
+        });
+    }
+    if l2_header.timestamp > super_root_proof.timestamp {
+        return Err(SuperRootError::BlockAfterTimestamp {
+            block_timestamp: l2_header.timestamp,
+            timestamp: super_root_proof.timestamp,
+        });
+    }
+    let chain_output_root = super_root_proof
+        .output_root(chain_id)
+        .ok_or(SuperRootError::ChainNotIncluded { chain_id })?;
+    if chain_output_root != output_root {
+        return Err(SuperRootError::OutputRootMismatch {
+            expected: output_root,
+            found: chain_output_root,
+        });
+    }
+    Ok(super_root_proof.super_root())
+}
+
+/// Validates that a chain's output root is part of a super root.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+/// * `l2_header` - The validated L2 header of the OpStack environment.
+/// * `output_root` - The output root recomputed from the L2 header.
+/// * `super_root_proof` - The super root preimage.
+///
+/// # Returns
+/// * `B256` - The super root.
+///
+/// # Panics
+/// Panics if the output root is not part of the super root, see [`SuperRootError`].
+pub fn validate_super_root_proof(
+    chain_id: u64,
+    l2_header: &Header,
+    output_root: B256,
+    super_root_proof: &SuperRootProof,
+) -> B256 {
+    match check_super_root_proof(chain_id, l2_header, output_root, super_root_proof) {
+        Ok(super_root) => super_root,
+        Err(err) => panic!("{err}"),
+    }
+}
+
 /// Retrieves validated block hash based on chain type and validation requirements.
 ///
 /// This function dispatches to the appropriate block hash validation logic depending on the chain type.
@@ -390,6 +1788,14 @@ pub fn validate_opstack_dispute_game_commitment(
 /// * `op_env_commitment` - Optional storage hash for L1 inclusion validation.
 /// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment for L2 chains.
 /// * `env_input_opstack_for_l1_block_call_2` - Optional second Optimism environment input for L1 validation.
+/// * `op_env_header` - Optional OpStack L2 header for output root recomputation.
+/// * `message_passer_proof` - Optional L2ToL1MessagePasser account proof for output root recomputation.
+/// * `super_root_proof` - Optional super root preimage, required for L1 inclusion via super root games.
+/// * `scroll_batch_index` - Optional index of the finalized batch ending at the block to validate, required for Scroll.
+/// * `opstack_rollup_config` - Optional rollup config of a configured OpStack chain.
+/// * `validated_ethereum_hash` - Optional Ethereum block hash already validated via OpStack, reused for L1 inclusion.
+/// * `eth_env_for_l1_signer` - Optional validated Ethereum environment from which built-in OpStack chains
+///   validated without L1 inclusion read their unsafe block signer.
 ///
 /// # Returns
 /// * `B256` - The validated block hash.
@@ -398,6 +1804,7 @@ pub fn validate_opstack_dispute_game_commitment(
 /// Panics if:
 /// * Chain ID is invalid or unsupported.
 /// * Validation fails for the specific chain type.
+#[allow(clippy::too_many_arguments)]
 pub fn get_validated_block_hash(
     chain_id: u64,
     env_header_to_validate: Header,
@@ -409,6 +1816,13 @@ pub fn get_validated_block_hash(
     op_env_commitment: Option<&Commitment>,
     sequencer_commitment_opstack_2: Option<SequencerCommitment>,
     env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+    op_env_header: Option<&Header>,
+    message_passer_proof: Option<&MessagePasserAccountProof>,
+    super_root_proof: Option<&SuperRootProof>,
+    scroll_batch_index: Option<u64>,
+    opstack_rollup_config: Option<&OpStackRollupConfig>,
+    validated_ethereum_hash: Option<B256>,
+    eth_env_for_l1_signer: Option<&EvmEnv<StateDb, EthEvmFactory, Commitment>>,
 ) -> B256 {
     // Dispatch to the correct validation logic based on chain type.
     if chain_id == LINEA_CHAIN_ID || chain_id == LINEA_SEPOLIA_CHAIN_ID {
@@ -422,11 +1836,13 @@ pub fn get_validated_block_hash(
             validate_l1_inclusion,
             sequencer_commitment_opstack_2,
             env_input_opstack_for_l1_block_call_2,
+            validated_ethereum_hash,
         )
     } else if chain_id == OPTIMISM_CHAIN_ID
         || chain_id == BASE_CHAIN_ID
         || chain_id == BASE_SEPOLIA_CHAIN_ID
         || chain_id == OPTIMISM_SEPOLIA_CHAIN_ID
+        || opstack_rollup_config.is_some()
     {
         get_validated_block_hash_opstack(
             chain_id,
@@ -438,6 +1854,25 @@ pub fn get_validated_block_hash(
             op_env_commitment,
             sequencer_commitment_opstack_2,
             env_input_opstack_for_l1_block_call_2,
+            op_env_header,
+            message_passer_proof,
+            super_root_proof,
+            opstack_rollup_config,
+            validated_ethereum_hash,
+            eth_env_for_l1_signer,
+        )
+    } else if chain_id == SCROLL_CHAIN_ID || chain_id == SCROLL_SEPOLIA_CHAIN_ID {
+        get_validated_block_hash_scroll(
+            chain_id,
+            sequencer_commitment_opstack,
+            env_input_opstack_for_l1_block_call,
+            env_input_eth_for_l1_inclusion,
+            block_header_to_validate,
+            validate_l1_inclusion,
+            sequencer_commitment_opstack_2,
+            env_input_opstack_for_l1_block_call_2,
+            scroll_batch_index.expect("scroll_batch_index is None"),
+            validated_ethereum_hash,
         )
     } else if chain_id == ETHEREUM_CHAIN_ID || chain_id == ETHEREUM_SEPOLIA_CHAIN_ID {
         get_validated_ethereum_block_hash_via_opstack(
@@ -466,6 +1901,13 @@ pub fn get_validated_block_hash(
 /// * `op_env_commitment` - Optional storage hash for L1 validation.
 /// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment.
 /// * `env_input_opstack_for_l1_block_call_2` - Optional second Optimism environment input.
+/// * `op_env_header` - Optional OpStack L2 header for output root recomputation.
+/// * `message_passer_proof` - Optional L2ToL1MessagePasser account proof for output root recomputation.
+/// * `super_root_proof` - Optional super root preimage, required for L1 inclusion via super root games.
+/// * `opstack_rollup_config` - Optional rollup config, used instead of the built-in parameters of the chain.
+/// * `validated_ethereum_hash` - Optional Ethereum block hash already validated via OpStack, reused for L1 inclusion.
+/// * `eth_env_for_l1_signer` - Optional validated Ethereum environment; without L1 inclusion, the sequencer
+///   commitment of a built-in chain is then verified against the unsafe block signer read from L1.
 ///
 /// # Returns
 /// * `B256` - The validated block hash.
@@ -474,6 +1916,7 @@ pub fn get_validated_block_hash(
 /// Panics if:
 /// * Validation fails for OpStack environment.
 /// * L1 inclusion validation fails when requested.
+#[allow(clippy::too_many_arguments)]
 pub fn get_validated_block_hash_opstack(
     chain_id: u64,
     sequencer_commitment: Option<SequencerCommitment>,
@@ -484,38 +1927,73 @@ pub fn get_validated_block_hash_opstack(
     op_env_commitment: Option<&Commitment>,
     sequencer_commitment_opstack_2: Option<SequencerCommitment>,
     env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+    op_env_header: Option<&Header>,
+    message_passer_proof: Option<&MessagePasserAccountProof>,
+    super_root_proof: Option<&SuperRootProof>,
+    opstack_rollup_config: Option<&OpStackRollupConfig>,
+    validated_ethereum_hash: Option<B256>,
+    eth_env_for_l1_signer: Option<&EvmEnv<StateDb, EthEvmFactory, Commitment>>,
 ) -> B256 {
     // Compute the hash of the block header to validate.
     let validated_hash = block_header_to_validate.hash_slow();
     if validate_l1_inclusion {
         // For L1 inclusion, determine the correct Ethereum chain ID.
-        let ethereum_chain_id = match chain_id {
-            OPTIMISM_CHAIN_ID | BASE_CHAIN_ID => ETHEREUM_CHAIN_ID,
-            OPTIMISM_SEPOLIA_CHAIN_ID | BASE_SEPOLIA_CHAIN_ID => ETHEREUM_SEPOLIA_CHAIN_ID,
-            _ => panic!("invalid chain id"),
-        };
+        let ethereum_chain_id = get_opstack_l1_chain_id(chain_id, opstack_rollup_config);
 
         // Validate the Ethereum block hash via OpStack.
-        let ethereum_hash = get_validated_ethereum_block_hash_via_opstack(
-            sequencer_commitment.as_ref(),
-            env_input_opstack_for_l1_block_call,
-            ethereum_chain_id,
-            sequencer_commitment_opstack_2.as_ref(),
-            env_input_opstack_for_l1_block_call_2,
-        );
+        let ethereum_hash = validated_ethereum_hash.unwrap_or_else(|| {
+            get_validated_ethereum_block_hash_via_opstack(
+                sequencer_commitment.as_ref(),
+                env_input_opstack_for_l1_block_call,
+                ethereum_chain_id,
+                sequencer_commitment_opstack_2.as_ref(),
+                env_input_opstack_for_l1_block_call_2,
+            )
+        });
 
         // Ensure the hashes match.
         assert_eq!(ethereum_hash, validated_hash, "hash mismatch  opstack");
         // Validate the OpStack dispute game commitment.
-        validate_opstack_dispute_game_commitment(
+        let eth_env = env_input_eth_for_l1_inclusion
+            .as_ref()
+            .unwrap()
+            .clone()
+            .into_env(get_eth_chain_spec(ethereum_chain_id));
+        let (portal_address, policy) = match opstack_rollup_config {
+            Some(rollup_config) => (
+                rollup_config.deposit_contract_address,
+                rollup_config.dispute_game_policy(),
+            ),
+            None => (
+                get_opstack_portal(chain_id),
+                get_dispute_game_policy(chain_id),
+            ),
+        };
+        validate_opstack_dispute_game_commitment_with_portal(
             chain_id,
-            env_input_eth_for_l1_inclusion
-                .as_ref()
-                .unwrap()
-                .clone()
-                .into_env(&ETH_MAINNET_CHAIN_SPEC),
+            portal_address,
+            &policy,
+            eth_env,
             op_env_commitment.unwrap(),
+            op_env_header.expect("op_env_header is None"),
+            message_passer_proof.expect("message_passer_proof is None"),
+            super_root_proof,
         )
+    } else if let Some(rollup_config) = opstack_rollup_config {
+        // For non-L1 inclusion of a configured chain, validate against the configured signer.
+        validate_opstack_env_with_rollup_config(
+            rollup_config,
+            &sequencer_commitment.unwrap(),
+            validated_hash,
+        );
+    } else if let Some(eth_env) = eth_env_for_l1_signer {
+        // For non-L1 inclusion with a validated Ethereum environment, validate against the signer on L1.
+        validate_opstack_env_with_l1_signer(
+            chain_id,
+            &sequencer_commitment.unwrap(),
+            validated_hash,
+            eth_env,
+        );
     } else {
         // For non-L1 inclusion, validate the OpStack environment directly.
         validate_opstack_env(chain_id, &sequencer_commitment.unwrap(), validated_hash);
@@ -537,6 +2015,7 @@ pub fn get_validated_block_hash_opstack(
 /// * `validate_l1_inclusion` - Whether to validate L1 inclusion.
 /// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment.
 /// * `env_input_opstack_for_l1_block_call_2` - Optional second Optimism environment input.
+/// * `validated_ethereum_hash` - Optional Ethereum block hash already validated via OpStack, reused for L1 inclusion.
 ///
 /// # Returns
 /// * `B256` - The validated block hash.
@@ -545,6 +2024,7 @@ pub fn get_validated_block_hash_opstack(
 /// Panics if:
 /// * Validation fails for Linea environment.
 /// * L1 inclusion validation fails when requested.
+#[allow(clippy::too_many_arguments)]
 pub fn get_validated_block_hash_linea(
     chain_id: u64,
     env_header_to_validate: Header,
@@ -555,6 +2035,7 @@ pub fn get_validated_block_hash_linea(
     validate_l1_inclusion: bool,
     sequencer_commitment_opstack_2: Option<SequencerCommitment>,
     env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+    validated_ethereum_hash: Option<B256>,
 ) -> B256 {
     if validate_l1_inclusion {
         // For L1 inclusion, determine the correct Ethereum chain ID.
@@ -564,13 +2045,15 @@ pub fn get_validated_block_hash_linea(
             _ => panic!("invalid chain id"),
         };
         // Validate the Ethereum block hash via OpStack.
-        let ethereum_hash = get_validated_ethereum_block_hash_via_opstack(
-            sequencer_commitment_opstack.as_ref(),
-            env_input_opstack_for_l1_block_call,
-            ethereum_chain_id,
-            sequencer_commitment_opstack_2.as_ref(),
-            env_input_opstack_for_l1_block_call_2,
-        );
+        let ethereum_hash = validated_ethereum_hash.unwrap_or_else(|| {
+            get_validated_ethereum_block_hash_via_opstack(
+                sequencer_commitment_opstack.as_ref(),
+                env_input_opstack_for_l1_block_call,
+                ethereum_chain_id,
+                sequencer_commitment_opstack_2.as_ref(),
+                env_input_opstack_for_l1_block_call_2,
+            )
+        });
         // Validate the Linea environment with L1 inclusion (block number only, not hash).
         validate_linea_env_with_l1_inclusion(
             chain_id,
@@ -579,9 +2062,23 @@ pub fn get_validated_block_hash_linea(
             ethereum_hash,
         );
     }
-    // Always validate the Linea environment (signature check).
-    validate_linea_env(chain_id, &block_header_to_validate);
-    block_header_to_validate.hash_slow()
+    // Always validate the Linea environment (signature check). QBFT validator sets announced on L1 are read
+    // through the Ethereum environment checked for L1 inclusion above.
+    let reads_l1_validators = matches!(
+        get_linea_consensus(chain_id, block_header_to_validate.number),
+        LineaConsensus::Qbft {
+            validators: QbftValidatorSet::L1 { .. }
+        }
+    );
+    let eth_env = (validate_l1_inclusion && reads_l1_validators).then(|| {
+        env_input_eth_for_l1_inclusion
+            .as_ref()
+            .unwrap()
+            .clone()
+            .into_env(get_eth_chain_spec(get_l1_chain_id(chain_id)))
+    });
+    validate_linea_env_with_l1_validators(chain_id, &block_header_to_validate, eth_env.as_ref());
+    get_block_hash(chain_id, block_header_to_validate.inner())
 }
 
 /// Executes batch multicall for proof data queries.
@@ -596,14 +2093,37 @@ pub fn get_validated_block_hash_linea(
 /// * `target_chain_ids` - Vector of target chain IDs.
 /// * `env` - EVM environment for contract calls.
 /// * `validate_l1_inclusion` - Whether L1 inclusion is being validated.
+/// * `sequencer_timestamp` - Timestamp of the sequencer-signed payload, or zero if none was used; consumers
+///   reject entries whose payload is older than their maximum age relative to their block timestamp.
+/// * `chain_config_hash` - Hash of the rollup config of a configured chain, or zero for built-in chains.
+/// * `market_registry` - Optional Operator in which every market must be listed.
 /// * `output` - Output vector for proof data results.
 ///
+/// # Returns
+/// * `Vec<(U256, U256)>` - The decoded `(amountIn, amountOut)` of each entry.
+///
+/// Each journal entry is packed as follows:
+///
+/// | Offset | Length | Data Type                   |
+/// |--------|--------|-----------------------------|
+/// | 0      | 20     | address sender              |
+/// | 20     | 20     | address market              |
+/// | 40     | 32     | uint256 accAmountIn         |
+/// | 72     | 32     | uint256 accAmountOut        |
+/// | 104    | 4      | uint32 chainId              |
+/// | 108    | 4      | uint32 dstChainId           |
+/// | 112    | 1      | bool L1inclusion            |
+/// | 113    | 8      | uint64 sequencerTimestamp   |
+/// | 121    | 32     | bytes32 chainConfigHash     |
+///
 /// # Panics
 /// Panics if:
 /// * Multicall execution fails.
 /// * Return data decoding fails.
 /// * Parameters are mismatched.
+/// * A market is not listed in the market registry.
 //~ zk coprocessor是 自己收集数据 而不是依赖合约事件的 journal 数据 
+#[allow(clippy::too_many_arguments)]
 pub fn batch_call_get_proof_data<H>(
     chain_id: u64,
     account: Vec<Address>,
@@ -611,70 +2131,220 @@ pub fn batch_call_get_proof_data<H>(
     target_chain_ids: Vec<u64>,
     env: EvmEnv<StateDb, H, Commitment>,
     validate_l1_inclusion: bool,
+    sequencer_timestamp: u64,
+    chain_config_hash: B256,
+    market_registry: Option<Address>,
     output: &mut Vec<Bytes>,
-) where
+) -> Vec<(U256, U256)>
+where
     H: Clone + std::fmt::Debug + EvmFactory,
 {
-    // Create array of Call3 structs for each proof data check.
-    let mut calls = Vec::with_capacity(account.len());
-    let batch_params = account
-        .iter()
-        .zip(asset.iter())
-        .zip(target_chain_ids.iter());
-    for ((user, market), target_chain_id) in batch_params {
-        let user_bytes: [u8; 32] = user.into_word().into();
-        let chain_id_bytes: [u8; 32] = U256::from(*target_chain_id).to_be_bytes();
-
-        // Create calldata by concatenating selector, encoded address, and chain ID.
-        let mut call_data = Vec::with_capacity(68); // 4 bytes selector + 32 bytes address + 32 bytes chain ID
-        call_data.extend_from_slice(&SELECTOR_MALDA_GET_PROOF_DATA);
-        call_data.extend_from_slice(&user_bytes);
-        call_data.extend_from_slice(&chain_id_bytes);
-
-        calls.push(Call3 {
-            target: *market,
-            allowFailure: false,
-            callData: call_data.into(),
-        });
-    }
-
     let multicall_contract = Contract::new(MULTICALL, &env);
 
     // Make single multicall.
-    let multicall = IMulticall3::aggregate3Call { calls };
+    let multicall = proof_data_multicall(&account, &asset, &target_chain_ids, market_registry);
 
     let returns = multicall_contract.call_builder(&multicall).call();
 
+    // Decode the amounts and reject unlisted markets.
+    let amounts = decode_proof_data_returns(&asset, &returns).unwrap_or_else(|err| panic!("{err}"));
+
     // Create a new iterator for the batch parameters to avoid cloning.
     let batch_params = account
         .iter()
         .zip(asset.iter())
         .zip(target_chain_ids.iter());
 
-    // Zip the batch parameters with returns for parallel iteration.
-    batch_params
-        .zip(returns.iter())
-        .for_each(|(((user, market), target_chain_id), result)| {
-            // Decode the returned data as a tuple of (amountIn, amountOut).
-            let amounts = <(U256, U256)>::abi_decode(&result.returnData)
-                .expect("Failed to decode return data");
-
-            let input = vec![
-                SolidityDataType::Address(*user),          // 用户地址
-                SolidityDataType::Address(*market),        // 市场地址
-                SolidityDataType::Number(amounts.0),       // amountIn（资产流入量）
-                SolidityDataType::Number(amounts.1),       // amountOut（资产流出量）
-                SolidityDataType::NumberWithShift(U256::from(chain_id), TakeLastXBytes(32)),       // 源链ID
-                SolidityDataType::NumberWithShift(U256::from(*target_chain_id), TakeLastXBytes(32)), // 目标链ID
-                SolidityDataType::Bool(validate_l1_inclusion),  // L1包含性验证标志
-            ];
+    // Zip the batch parameters with the amounts for parallel iteration.
+    for (((user, market), target_chain_id), amounts) in batch_params.zip(amounts.iter()) {
+        output.push(encode_proof_data_journal_entry(
+            chain_id,
+            *user,
+            *market,
+            *amounts,
+            *target_chain_id,
+            validate_l1_inclusion,
+            sequencer_timestamp,
+            chain_config_hash,
+        ));
+    }
+    amounts
+}
 
-            let (bytes, _hash) = abi::encode_packed(&input);
-            output.push(bytes.into());
-        });
+/// Errors returned when the results of the proof data multicall are invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofDataError {
+    /// The multicall returned a different number of results than it made calls.
+    ResultCount { expected: usize, found: usize },
+    /// The return data of an entry is not an `(amountIn, amountOut)` tuple.
+    InvalidAmounts { index: usize },
+    /// The return data of a listing check is not a boolean.
+    InvalidListing { index: usize },
+    /// A market is not listed in the market registry.
+    MarketNotListed { market: Address },
 }
 
-/// Validates Linea environment with L1 inclusion verification.
+impl core::fmt::Display for ProofDataError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ResultCount { expected, found } => {
+                write!(f, "multicall returned {found} results, expected {expected}")
+            }
+            Self::InvalidAmounts { index } => {
+                write!(f, "Failed to decode return data of entry {index}")
+            }
+            Self::InvalidListing { index } => {
+                write!(f, "Failed to decode listing of entry {index}")
+            }
+            Self::MarketNotListed { market } => write!(f, "market not listed: {market}"),
+        }
+    }
+}
+
+impl std::error::Error for ProofDataError {}
+
+/// Decodes the results of the multicall built by [`proof_data_multicall`].
+///
+/// # Arguments
+/// * `asset` - The market of each entry.
+/// * `returns` - The multicall results: one `getProofData` result per entry, followed by one listing check per
+///   market if a registry was queried.
+///
+/// # Returns
+/// * `Result<Vec<(U256, U256)>, ProofDataError>` - The `(amountIn, amountOut)` of each entry, if every queried
+///   market is listed.
+pub fn decode_proof_data_returns(
+    asset: &[Address],
+    returns: &[IMulticall3::Result],
+) -> Result<Vec<(U256, U256)>, ProofDataError> {
+    if returns.len() != asset.len() && returns.len() != 2 * asset.len() {
+        return Err(ProofDataError::ResultCount {
+            expected: asset.len(),
+            found: returns.len(),
+        });
+    }
+
+    // Split off the registry answers and reject unlisted markets.
+    let (returns, listed) = returns.split_at(asset.len());
+    for (index, (market, result)) in asset.iter().zip(listed.iter()).enumerate() {
+        let is_listed = bool::abi_decode(&result.returnData)
+            .map_err(|_| ProofDataError::InvalidListing { index })?;
+        if !is_listed {
+            return Err(ProofDataError::MarketNotListed { market: *market });
+        }
+    }
+
+    // Decode the returned data of each entry as a tuple of (amountIn, amountOut).
+    returns
+        .iter()
+        .enumerate()
+        .map(|(index, result)| {
+            <(U256, U256)>::abi_decode(&result.returnData)
+                .map_err(|_| ProofDataError::InvalidAmounts { index })
+        })
+        .collect()
+}
+
+/// Packs one journal entry, see [`batch_call_get_proof_data`] for the layout.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID the proof is generated for.
+/// * `user` - The account of the entry.
+/// * `market` - The market of the entry.
+/// * `amounts` - The `(amountIn, amountOut)` of the entry.
+/// * `target_chain_id` - The target chain ID of the entry.
+/// * `validate_l1_inclusion` - Whether L1 inclusion was validated.
+/// * `sequencer_timestamp` - Timestamp of the sequencer-signed payload, or zero if none was used.
+/// * `chain_config_hash` - Hash of the rollup config of a configured chain, or zero for built-in chains.
+///
+/// # Returns
+/// * `Bytes` - The packed journal entry.
+#[allow(clippy::too_many_arguments)]
+pub fn encode_proof_data_journal_entry(
+    chain_id: u64,
+    user: Address,
+    market: Address,
+    amounts: (U256, U256),
+    target_chain_id: u64,
+    validate_l1_inclusion: bool,
+    sequencer_timestamp: u64,
+    chain_config_hash: B256,
+) -> Bytes {
+    let input = vec![
+        SolidityDataType::Address(user),          // 用户地址
+        SolidityDataType::Address(market),        // 市场地址
+        SolidityDataType::Number(amounts.0),       // amountIn（资产流入量）
+        SolidityDataType::Number(amounts.1),       // amountOut（资产流出量）
+        SolidityDataType::NumberWithShift(U256::from(chain_id), TakeLastXBytes(32)),       // 源链ID
+        SolidityDataType::NumberWithShift(U256::from(target_chain_id), TakeLastXBytes(32)), // 目标链ID
+        SolidityDataType::Bool(validate_l1_inclusion),  // L1包含性验证标志
+        SolidityDataType::NumberWithShift(U256::from(sequencer_timestamp), TakeLastXBytes(64)),
+        SolidityDataType::Bytes(chain_config_hash.as_slice()),
+    ];
+
+    let (bytes, _hash) = abi::encode_packed(&input);
+    bytes.into()
+}
+
+/// Builds the multicall executed by [`batch_call_get_proof_data`].
+///
+/// Hosts preflight the same call to collect the state the guest needs.
+///
+/// # Arguments
+/// * `account` - Account addresses to query.
+/// * `asset` - Market addresses to query.
+/// * `target_chain_ids` - Target chain IDs for each account.
+/// * `market_registry` - Optional registry every market must be listed in.
+///
+/// # Returns
+/// * `IMulticall3::aggregate3Call` - One `getProofData` call per account, followed by one listing check per
+///   market if a registry is given.
+pub fn proof_data_multicall(
+    account: &[Address],
+    asset: &[Address],
+    target_chain_ids: &[u64],
+    market_registry: Option<Address>,
+) -> IMulticall3::aggregate3Call {
+    // Create array of Call3 structs for each proof data check.
+    let mut calls = Vec::with_capacity(account.len());
+    let batch_params = account
+        .iter()
+        .zip(asset.iter())
+        .zip(target_chain_ids.iter());
+    for ((user, market), target_chain_id) in batch_params {
+        let user_bytes: [u8; 32] = user.into_word().into();
+        let chain_id_bytes: [u8; 32] = U256::from(*target_chain_id).to_be_bytes();
+
+        // Create calldata by concatenating selector, encoded address, and chain ID.
+        let mut call_data = Vec::with_capacity(68); // 4 bytes selector + 32 bytes address + 32 bytes chain ID
+        call_data.extend_from_slice(&SELECTOR_MALDA_GET_PROOF_DATA);
+        call_data.extend_from_slice(&user_bytes);
+        call_data.extend_from_slice(&chain_id_bytes);
+
+        calls.push(Call3 {
+            target: *market,
+            allowFailure: false,
+            callData: call_data.into(),
+        });
+    }
+
+    // Check that each market is listed in the registry within the same multicall.
+    if let Some(registry) = market_registry {
+        for market in asset.iter() {
+            calls.push(Call3 {
+                target: registry,
+                allowFailure: false,
+                callData: IMarketRegistry::isMarketListedCall { market: *market }
+                    .abi_encode()
+                    .into(),
+            });
+        }
+    }
+
+    IMulticall3::aggregate3Call { calls }
+}
+
+/// Validates Linea environment with L1 inclusion verification.
 ///
 /// This function verifies that a Linea block is properly included in the L1 chain
 /// by checking block numbers. This is not sufficient to check L1 inclusion which would require checking the hash. This is currently not possible
@@ -704,7 +2374,9 @@ pub fn validate_linea_env_with_l1_inclusion(
         _ => panic!("invalid chain id"),
     };
 
-    let env_eth = env_eth_input.clone().into_env(&ETH_MAINNET_CHAIN_SPEC);
+    let env_eth = env_eth_input
+        .clone()
+        .into_env(get_eth_chain_spec(get_l1_chain_id(chain_id)));
 
     let eth_hash = env_eth.header().seal();
 
@@ -725,216 +2397,3907 @@ pub fn validate_linea_env_with_l1_inclusion(
     );
 }
 
-/// Validates a Linea block header by verifying the sequencer signature.
+/// Scroll mainnet chain ID.
+pub const SCROLL_CHAIN_ID: u64 = 534352;
+/// Scroll Sepolia chain ID.
+pub const SCROLL_SEPOLIA_CHAIN_ID: u64 = 534351;
+
+/// ScrollChain rollup contract of Scroll mainnet on Ethereum.
+pub const SCROLL_CHAIN: Address = address!("a13baf47339d63b743e7da8741db5456dac1e556");
+/// ScrollChain rollup contract of Scroll Sepolia on Ethereum Sepolia.
+pub const SCROLL_SEPOLIA_CHAIN: Address = address!("2d567ece699eabe5afcd141edb7a4f2d0d6ce8a0");
+
+/// Reorg protection on Scroll. The batch only authenticates the state root of its last block, not its hash,
+/// so the view call block must be that block: a linking header carrying the state root could claim any parent.
+pub const REORG_PROTECTION_POLICY_SCROLL: ReorgProtectionPolicy =
+    ReorgProtectionPolicy::blocks(0).with_max_blocks(0);
+
+sol! {
+    /// Batch finalization getters of the ScrollChain rollup contract.
+    interface IScrollChain {
+        function lastFinalizedBatchIndex() external view returns (uint256);
+        function finalizedStateRoots(uint256 batchIndex) external view returns (bytes32);
+    }
+}
+
+/// Validates Scroll block hash through a batch finalized on L1.
 ///
-/// This function checks that the block is signed by the official Linea sequencer by extracting the signature from the extra data,
-/// recovering the signer, and comparing it to the expected sequencer address for the given chain.
+/// Scroll blocks carry no sequencer signature, so L1 inclusion is mandatory: the Ethereum block is validated
+/// via OpStack, and the block to validate must be the last block of a batch finalized in the ScrollChain contract.
+/// Only its state root is authenticated, so [`REORG_PROTECTION_POLICY_SCROLL`] requires it to be the view call
+/// block, whose state the view call reads.
 ///
 /// # Arguments
-/// * `chain_id` - The chain ID (Linea mainnet or Sepolia).
-/// * `block_header_to_validate` - The Linea block header to validate.
+/// * `chain_id` - The Scroll chain ID.
+/// * `sequencer_commitment_opstack` - Optional sequencer commitment.
+/// * `env_input_opstack_for_l1_block_call` - Optional Optimism environment input.
+/// * `env_input_eth_for_l1_inclusion` - Optional Ethereum environment input.
+/// * `block_header_to_validate` - The view call block, the last block of the finalized batch.
+/// * `validate_l1_inclusion` - Whether to validate L1 inclusion, must be set.
+/// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment.
+/// * `env_input_opstack_for_l1_block_call_2` - Optional second Optimism environment input.
+/// * `batch_index` - Index of the finalized batch ending at the block to validate.
+/// * `validated_ethereum_hash` - Optional Ethereum block hash already validated via OpStack, reused for L1 inclusion.
+///
+/// # Returns
+/// * `B256` - The validated block hash.
 ///
 /// # Panics
 /// Panics if:
-/// * Chain ID is not a Linea chain.
-/// * Block is not signed by the official Linea sequencer.
-/// * Signature recovery fails.
-/// * Extra data format is invalid.
-pub fn validate_linea_env(chain_id: u64, block_header_to_validate: &RlpHeader<Header>) {
-    // Extract the extra data and split into prefix and signature.
-    let extra_data = block_header_to_validate.inner().extra_data.clone();
+/// * L1 inclusion is not requested.
+/// * Ethereum block validation fails.
+/// * Validation fails for the Scroll environment.
+#[allow(clippy::too_many_arguments)]
+pub fn get_validated_block_hash_scroll(
+    chain_id: u64,
+    sequencer_commitment_opstack: Option<SequencerCommitment>,
+    env_input_opstack_for_l1_block_call: Option<EthEvmInput>,
+    env_input_eth_for_l1_inclusion: &Option<EthEvmInput>,
+    block_header_to_validate: RlpHeader<Header>,
+    validate_l1_inclusion: bool,
+    sequencer_commitment_opstack_2: Option<SequencerCommitment>,
+    env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+    batch_index: u64,
+    validated_ethereum_hash: Option<B256>,
+) -> B256 {
+    assert!(validate_l1_inclusion, "scroll requires l1 inclusion");
 
-    let length = extra_data.len();
-    let prefix = extra_data.slice(0..length - 65);
-    let signature_bytes = extra_data.slice(length - 65..length);
+    // Determine the correct Ethereum chain ID.
+    let ethereum_chain_id = match chain_id {
+        SCROLL_CHAIN_ID => ETHEREUM_CHAIN_ID,
+        SCROLL_SEPOLIA_CHAIN_ID => ETHEREUM_SEPOLIA_CHAIN_ID,
+        _ => panic!("invalid chain id"),
+    };
+    // Validate the Ethereum block hash via OpStack.
+    let ethereum_hash = validated_ethereum_hash.unwrap_or_else(|| {
+        get_validated_ethereum_block_hash_via_opstack(
+            sequencer_commitment_opstack.as_ref(),
+            env_input_opstack_for_l1_block_call,
+            ethereum_chain_id,
+            sequencer_commitment_opstack_2.as_ref(),
+            env_input_opstack_for_l1_block_call_2,
+        )
+    });
+    // Validate that the block closes a batch finalized on L1.
+    validate_scroll_env_with_l1_inclusion(
+        chain_id,
+        &block_header_to_validate,
+        batch_index,
+        env_input_eth_for_l1_inclusion.as_ref().unwrap(),
+        ethereum_hash,
+    );
+    block_header_to_validate.hash_slow()
+}
 
-    let sig = signature_from_bytes(
-        &signature_bytes
-            .try_into()
-            .expect("Failed to convert signature bytes to fixed array"),
+/// Validates Scroll environment with L1 inclusion verification.
+///
+/// This function verifies that a Scroll block is the last block of a batch finalized in the ScrollChain contract
+/// by comparing its state root with the finalized state root of the batch.
+///
+/// # Arguments
+/// * `chain_id` - The Scroll chain ID.
+/// * `block_header_to_validate` - The last block of the finalized batch.
+/// * `batch_index` - Index of the finalized batch.
+/// * `env_eth_input` - The Ethereum EVM input for L1 validation.
+/// * `ethereum_hash` - The Ethereum block hash to validate against.
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is invalid.
+/// * Ethereum hash doesn't match.
+/// * The batch is not finalized.
+/// * The state root doesn't match the finalized state root of the batch.
+pub fn validate_scroll_env_with_l1_inclusion(
+    chain_id: u64,
+    block_header_to_validate: &RlpHeader<Header>,
+    batch_index: u64,
+    env_eth_input: &EthEvmInput,
+    ethereum_hash: B256,
+) {
+    // Select the correct rollup contract for the given chain.
+    let scroll_chain_address = match chain_id {
+        SCROLL_CHAIN_ID => SCROLL_CHAIN,
+        SCROLL_SEPOLIA_CHAIN_ID => SCROLL_SEPOLIA_CHAIN,
+        _ => panic!("invalid chain id"),
+    };
+
+    let env_eth = env_eth_input
+        .clone()
+        .into_env(get_eth_chain_spec(get_l1_chain_id(chain_id)));
+
+    // Ensure the Ethereum hash matches.
+    assert_eq!(
+        ethereum_hash,
+        env_eth.header().seal(),
+        "Ethereum hash mismatch scroll"
     );
 
-    // Remove the signature from the header for sighash calculation.
-    let mut header = block_header_to_validate.inner().clone();
-    header.extra_data = prefix;
+    let contract = Contract::new(scroll_chain_address, &env_eth);
 
-    let sighash: [u8; 32] = header
-        .hash_slow()
-        .to_vec()
-        .try_into()
-        .expect("Failed to convert header hash to fixed array");
-    let sighash = B256::new(sighash);
+    let last_finalized_batch_index = contract
+        .call_builder(&IScrollChain::lastFinalizedBatchIndexCall {})
+        .call();
+    assert!(
+        U256::from(batch_index) <= last_finalized_batch_index,
+        "Batch not finalized on L1"
+    );
 
-    // Recover the sequencer address from the signature and sighash.
-    let sequencer =
-        recover_signer(sig, sighash).expect("Failed to recover sequencer address from signature");
+    let finalized_state_root = contract
+        .call_builder(&IScrollChain::finalizedStateRootsCall {
+            batchIndex: U256::from(batch_index),
+        })
+        .call();
+    assert_eq!(
+        block_header_to_validate.state_root, finalized_state_root,
+        "state root mismatch scroll"
+    );
+}
 
-    // Determine the expected sequencer address for the given chain.
-    let expected_sequencer = match chain_id {
-        LINEA_CHAIN_ID => LINEA_SEQUENCER,
-        LINEA_SEPOLIA_CHAIN_ID => LINEA_SEPOLIA_SEQUENCER,
-        _ => panic!("invalid chain id"),
-    };
+/// A sequencer address together with the range of blocks it signs.
+///
+/// Rotations are staged by closing the current entry at the last block of the old key and adding an
+/// entry for the new key starting at the next block, so proofs spanning a rotation validate correctly.
+/// Schedules must list entries in block order without overlaps, see [`check_sequencer_schedule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SequencerScheduleEntry {
+    pub sequencer: Address,
+    /// First block signed by this sequencer.
+    pub valid_from_block: u64,
+    /// Last block signed by this sequencer, or `None` if the key is still active.
+    pub valid_until_block: Option<u64>,
+}
 
-    // Ensure the recovered sequencer matches the expected address.
-    if sequencer != expected_sequencer {
-        panic!("Block not signed by linea sequencer");
+impl SequencerScheduleEntry {
+    /// Returns whether this entry covers the given block.
+    pub fn covers(&self, block_number: u64) -> bool {
+        block_number >= self.valid_from_block
+            && self
+                .valid_until_block
+                .is_none_or(|valid_until_block| block_number <= valid_until_block)
     }
 }
 
-/// Validates an OpStack (Optimism/Base) environment through sequencer commitments.
+/// Errors returned when a sequencer schedule is malformed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SequencerScheduleError {
+    /// An entry ends before it starts.
+    EmptyRange { index: usize },
+    /// An entry starts before the end of the previous entry, or the previous entry is still open.
+    Overlap { index: usize },
+}
+
+impl core::fmt::Display for SequencerScheduleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::EmptyRange { index } => write!(f, "sequencer schedule entry {index} is empty"),
+            Self::Overlap { index } => write!(
+                f,
+                "sequencer schedule entry {index} overlaps the previous entry"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SequencerScheduleError {}
+
+/// Checks that a sequencer schedule lists non-empty entries in block order without overlaps.
 ///
-/// This function verifies the sequencer commitment for OpStack chains, checks the signature, and ensures the block hash matches.
+/// Every entry but the last must be closed, so that at most one sequencer covers each block.
 ///
 /// # Arguments
-/// * `chain_id` - The chain ID (Optimism or Base, mainnet or Sepolia).
-/// * `commitment` - The sequencer commitment to verify.
-/// * `env_block_hash` - The block hash to validate against.
+/// * `schedule` - The sequencer schedule.
+///
+/// # Returns
+/// * `Result<(), SequencerScheduleError>` - `Ok` if the schedule is well formed.
+pub fn check_sequencer_schedule(
+    schedule: &[SequencerScheduleEntry],
+) -> Result<(), SequencerScheduleError> {
+    let mut previous_until_block = None;
+    for (index, entry) in schedule.iter().enumerate() {
+        if entry
+            .valid_until_block
+            .is_some_and(|valid_until_block| valid_until_block < entry.valid_from_block)
+        {
+            return Err(SequencerScheduleError::EmptyRange { index });
+        }
+        if index > 0 {
+            match previous_until_block {
+                Some(until_block) if entry.valid_from_block > until_block => {}
+                _ => return Err(SequencerScheduleError::Overlap { index }),
+            }
+        }
+        previous_until_block = entry.valid_until_block;
+    }
+    Ok(())
+}
+
+const OPTIMISM_SEQUENCER_SCHEDULE: &[SequencerScheduleEntry] = &[SequencerScheduleEntry {
+    sequencer: OPTIMISM_SEQUENCER,
+    valid_from_block: 0,
+    valid_until_block: None,
+}];
+const BASE_SEQUENCER_SCHEDULE: &[SequencerScheduleEntry] = &[SequencerScheduleEntry {
+    sequencer: BASE_SEQUENCER,
+    valid_from_block: 0,
+    valid_until_block: None,
+}];
+const LINEA_SEQUENCER_SCHEDULE: &[SequencerScheduleEntry] = &[SequencerScheduleEntry {
+    sequencer: LINEA_SEQUENCER,
+    valid_from_block: 0,
+    valid_until_block: None,
+}];
+const OPTIMISM_SEPOLIA_SEQUENCER_SCHEDULE: &[SequencerScheduleEntry] = &[SequencerScheduleEntry {
+    sequencer: OPTIMISM_SEPOLIA_SEQUENCER,
+    valid_from_block: 0,
+    valid_until_block: None,
+}];
+const BASE_SEPOLIA_SEQUENCER_SCHEDULE: &[SequencerScheduleEntry] = &[SequencerScheduleEntry {
+    sequencer: BASE_SEPOLIA_SEQUENCER,
+    valid_from_block: 0,
+    valid_until_block: None,
+}];
+const LINEA_SEPOLIA_SEQUENCER_SCHEDULE: &[SequencerScheduleEntry] = &[SequencerScheduleEntry {
+    sequencer: LINEA_SEPOLIA_SEQUENCER,
+    valid_from_block: 0,
+    valid_until_block: None,
+}];
+
+/// Returns the sequencer schedule for the given chain.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID (Linea or OpStack, mainnet or Sepolia).
+///
+/// # Returns
+/// * `&'static [SequencerScheduleEntry]` - The sequencer schedule of the chain.
 ///
 /// # Panics
 /// Panics if:
-/// * Chain ID is not an OpStack chain.
-/// * Commitment verification fails.
-/// * Block hash doesn't match commitment.
-/// * Sequencer signature is invalid.
-/// * Execution payload conversion fails.
-pub fn validate_opstack_env(chain_id: u64, commitment: &SequencerCommitment, env_block_hash: B256) {
-    // Verify the sequencer commitment for the correct chain and sequencer address.
-    match chain_id {
-        OPTIMISM_CHAIN_ID => commitment
-            .verify(OPTIMISM_SEQUENCER, OPTIMISM_CHAIN_ID)
-            .expect("Failed to verify Optimism sequencer commitment"),
-        BASE_CHAIN_ID => commitment
-            .verify(BASE_SEQUENCER, BASE_CHAIN_ID)
-            .expect("Failed to verify Base sequencer commitment"),
-        OPTIMISM_SEPOLIA_CHAIN_ID => commitment
-            .verify(OPTIMISM_SEPOLIA_SEQUENCER, OPTIMISM_SEPOLIA_CHAIN_ID)
-            .expect("Failed to verify Optimism Sepolia sequencer commitment"),
-        BASE_SEPOLIA_CHAIN_ID => commitment
-            .verify(BASE_SEPOLIA_SEQUENCER, BASE_SEPOLIA_CHAIN_ID)
-            .expect("Failed to verify Base Sepolia sequencer commitment"),
+/// * Chain ID is invalid or unsupported.
+/// * The schedule fails [`check_sequencer_schedule`].
+pub fn get_sequencer_schedule(chain_id: u64) -> &'static [SequencerScheduleEntry] {
+    let schedule = match chain_id {
+        OPTIMISM_CHAIN_ID => OPTIMISM_SEQUENCER_SCHEDULE,
+        BASE_CHAIN_ID => BASE_SEQUENCER_SCHEDULE,
+        LINEA_CHAIN_ID => LINEA_SEQUENCER_SCHEDULE,
+        OPTIMISM_SEPOLIA_CHAIN_ID => OPTIMISM_SEPOLIA_SEQUENCER_SCHEDULE,
+        BASE_SEPOLIA_CHAIN_ID => BASE_SEPOLIA_SEQUENCER_SCHEDULE,
+        LINEA_SEPOLIA_CHAIN_ID => LINEA_SEPOLIA_SEQUENCER_SCHEDULE,
         _ => panic!("invalid chain id"),
+    };
+    if let Err(err) = check_sequencer_schedule(schedule) {
+        panic!("{err}");
     }
-    // Convert the commitment to an execution payload and check the block hash.
-    let payload = ExecutionPayload::try_from(commitment)
-        .expect("Failed to convert sequencer commitment to execution payload");
-    assert_eq!(payload.block_hash, env_block_hash, "block hash mismatch");
+    schedule
 }
 
-/// Retrieves and validates Ethereum L1 block hash through OpStack L2.
-///
-/// Uses Optimism's L1Block contract to fetch and verify the L1 block hash.
-/// This provides a secure way to verify L1 block hashes through L2 commitments.
+/// Returns the sequencer scheduled to sign the given block.
 ///
 /// # Arguments
-/// * `sequencer_commitment_opstack_1` - The Optimism sequencer commitment.
-/// * `env_input_opstack_for_l1_block_call_1` - The Optimism EVM input containing environment data.
-/// * `chain_id` - The Ethereum chain ID (mainnet or Sepolia).
-/// * `_sequencer_commitment_opstack_2` - (Unused) Optional second sequencer commitment.
-/// * `_env_input_opstack_for_l1_block_call_2` - (Unused) Optional second Optimism EVM input.
+/// * `chain_id` - The chain ID (Linea or OpStack, mainnet or Sepolia).
+/// * `block_number` - The block number.
 ///
 /// # Returns
-/// * `B256` - The validated Ethereum block hash.
+/// * `Address` - The expected sequencer address.
 ///
 /// # Panics
 /// Panics if:
-/// * OpStack environment validation fails.
-/// * L1Block contract call fails.
-/// * Chain ID is not an Ethereum chain.
-pub fn get_validated_ethereum_block_hash_via_opstack(
-    sequencer_commitment_opstack_1: Option<&SequencerCommitment>,
-    env_input_opstack_for_l1_block_call_1: Option<EthEvmInput>,
-    chain_id: u64,
-    _sequencer_commitment_opstack_2: Option<&SequencerCommitment>,
-    _env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
-) -> B256 {
-    // Convert the provided EVM input to an environment.
-    let env_op = env_input_opstack_for_l1_block_call_1
-        .expect("env_input_opstack_for_l1_block_call_1 is None")
-        .into_env(&ETH_MAINNET_CHAIN_SPEC);
+/// * Chain ID is invalid or unsupported.
+/// * No sequencer is scheduled for the block.
+pub fn get_expected_sequencer(chain_id: u64, block_number: u64) -> Address {
+    get_sequencer_schedule(chain_id)
+        .iter()
+        .find(|entry| entry.covers(block_number))
+        .expect("no sequencer scheduled for block")
+        .sequencer
+}
 
-    // Determine which OpStack chain to use for validation.
-    let (verify_via_chain_1, _verify_via_chain_2) = if chain_id == ETHEREUM_CHAIN_ID {
-        (OPTIMISM_CHAIN_ID, BASE_CHAIN_ID)
-    } else {
-        (OPTIMISM_SEPOLIA_CHAIN_ID, BASE_SEPOLIA_CHAIN_ID)
-    };
+/// Length of the vanity prefix of Linea extra data.
+pub const LINEA_EXTRA_DATA_VANITY_LEN: usize = 32;
+/// Length of the sequencer seal at the end of Linea extra data.
+pub const LINEA_EXTRA_DATA_SEAL_LEN: usize = 65;
+/// Supported values of the version byte at the start of the Linea vanity prefix.
+pub const LINEA_EXTRA_DATA_VERSIONS: &[u8] = &[0x00, 0x01];
 
-    // Validate the OpStack environment and commitment.
-    validate_opstack_env(
-        verify_via_chain_1,
-        sequencer_commitment_opstack_1.unwrap(),
-        env_op.commitment().digest,
-    );
+/// Linea extra data split into its vanity prefix and sequencer seal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineaExtraData {
+    /// Vanity prefix, starting with the version byte. This is the extra data the seal signs over.
+    pub vanity: Bytes,
+    /// 65-byte sequencer signature.
+    pub signature: Bytes,
+}
 
-    // Query the L1 block hash from the L1Block contract.
-    let l1_block = Contract::new(L1_BLOCK_ADDRESS_OPSTACK, &env_op);
-    let call = IL1Block::hashCall {};
-    let l1_hash_1 = l1_block.call_builder(&call).call();
+impl LineaExtraData {
+    /// Returns the version byte of the extra data.
+    pub fn version(&self) -> u8 {
+        self.vanity[0]
+    }
+}
 
-    // (Optional) Could validate via a second chain, but currently omitted.
-    // let env_op_2 = env_input_opstack_for_l1_block_call_2.expect("env_input_opstack_for_l1_block_call_2 is None").into_env();
-    // validate_opstack_env(verify_via_chain_2, sequencer_commitment_opstack_2.unwrap(), env_op_2.commitment().digest);
-    // let l1_block = Contract::new(L1_BLOCK_ADDRESS_OPSTACK, &env_op_2);
-    // let call = IL1Block::hashCall {};
-    // let l1_hash_2 = l1_block.call_builder(&call).call().0;
-    // assert_eq!(l1_hash_1, l1_hash_2, "L1 hash 1 and 2 mismatch");
+/// Errors returned when Linea extra data doesn't follow the expected layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LineaExtraDataError {
+    /// The extra data doesn't have the length of a vanity prefix followed by a seal.
+    InvalidLength { length: usize, expected: usize },
+    /// The version byte of the vanity prefix is not supported.
+    UnsupportedVersion { version: u8 },
+}
 
-    l1_hash_1
+impl core::fmt::Display for LineaExtraDataError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidLength { length, expected } => write!(
+                f,
+                "invalid linea extra data length: {length}, expected {expected}"
+            ),
+            Self::UnsupportedVersion { version } => {
+                write!(f, "unsupported linea extra data version: {version:#04x}")
+            }
+        }
+    }
 }
 
-/// Validates block chain length and hash linking for reorg protection.
+impl std::error::Error for LineaExtraDataError {}
+
+/// Parses Linea extra data into its vanity prefix and sequencer seal.
 ///
-/// Ensures sufficient block confirmations and proper hash linking between blocks
-/// to prevent reorganization attacks. Checks that the chain is long enough, that each block is hash-linked to its parent,
-/// and that the final hash matches the expected current hash.
+/// Linea extra data consists of a 32-byte vanity prefix, whose first byte is a format version,
+/// followed by the 65-byte sequencer signature over the header without the seal.
 ///
 /// # Arguments
-/// * `chain_id` - The chain ID to determine reorg protection depth.
-/// * `historical_hash` - The hash of the historical block.
-/// * `linking_blocks` - Vector of blocks linking historical to current.
-/// * `current_hash` - The expected current block hash.
+/// * `extra_data` - The extra data of a Linea header.
+///
+/// # Returns
+/// * `Result<LineaExtraData, LineaExtraDataError>` - The parsed extra data.
+pub fn parse_linea_extra_data(extra_data: &Bytes) -> Result<LineaExtraData, LineaExtraDataError> {
+    let expected = LINEA_EXTRA_DATA_VANITY_LEN + LINEA_EXTRA_DATA_SEAL_LEN;
+    if extra_data.len() != expected {
+        return Err(LineaExtraDataError::InvalidLength {
+            length: extra_data.len(),
+            expected,
+        });
+    }
+    let version = extra_data[0];
+    if !LINEA_EXTRA_DATA_VERSIONS.contains(&version) {
+        return Err(LineaExtraDataError::UnsupportedVersion { version });
+    }
+    Ok(LineaExtraData {
+        vanity: extra_data.slice(..LINEA_EXTRA_DATA_VANITY_LEN),
+        signature: extra_data.slice(LINEA_EXTRA_DATA_VANITY_LEN..),
+    })
+}
+
+/// Validates a Linea block header according to the consensus active at its block.
+///
+/// Checks the header with [`check_linea_seals`]. Only configured QBFT validator sets are supported; see
+/// [`validate_linea_env_with_l1_validators`] for validator sets read from L1.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID (Linea mainnet or Sepolia).
+/// * `block_header_to_validate` - The Linea block header to validate.
 ///
 /// # Panics
 /// Panics if:
-/// * Chain length is less than required reorg protection depth.
-/// * Blocks are not properly hash-linked.
-/// * Final hash doesn't match current hash.
-/// * Chain ID is invalid or unsupported.
-pub fn validate_chain_length(
+/// * Chain ID is not a Linea chain.
+/// * Validation fails for the active consensus.
+/// * The QBFT validator set of the block is read from L1.
+pub fn validate_linea_env(chain_id: u64, block_header_to_validate: &RlpHeader<Header>) {
+    validate_linea_env_with_l1_validators(chain_id, block_header_to_validate, None)
+}
+
+/// Validates a Linea block header, reading QBFT validator sets announced on L1 through an Ethereum environment.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID (Linea mainnet or Sepolia).
+/// * `block_header_to_validate` - The Linea block header to validate.
+/// * `eth_env` - Optional validated Ethereum EVM environment, required if the validator set is read from L1.
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is not a Linea chain.
+/// * No consensus is scheduled for the block.
+/// * The header fails [`check_linea_seals`] for the active consensus.
+pub fn validate_linea_env_with_l1_validators(
     chain_id: u64,
-    historical_hash: B256,
-    linking_blocks: &Vec<RlpHeader<Header>>,
-    current_hash: B256,
+    block_header_to_validate: &RlpHeader<Header>,
+    eth_env: Option<&EvmEnv<StateDb, EthEvmFactory, Commitment>>,
 ) {
-    // Determine the required reorg protection depth for the given chain.
-    let reorg_protection_depth = match chain_id {
-        OPTIMISM_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM,
-        BASE_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE,
-        LINEA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA,
-        ETHEREUM_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM,
-        OPTIMISM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
-        BASE_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
-        LINEA_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
-        ETHEREUM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
-        _ => panic!("invalid chain id"),
-    };
-    let chain_length = linking_blocks.len() as u64;
-    // Ensure the chain is long enough for reorg protection.
-    assert!(
-        chain_length >= reorg_protection_depth,
-        "chain length is less than reorg protection"
-    );
-    let mut previous_hash = historical_hash;
-    // Check that each block is hash-linked to its parent.
-    for header in linking_blocks.iter() {
-        let parent_hash = header.parent_hash;
-        assert_eq!(parent_hash, previous_hash, "blocks not hashlinked");
-        previous_hash = header.hash_slow();
+    let consensus = get_linea_consensus(chain_id, block_header_to_validate.number);
+    if let Err(err) = check_linea_seals(
+        &consensus,
+        chain_id,
+        block_header_to_validate.inner(),
+        eth_env,
+    ) {
+        panic!("{err}");
     }
-    // Ensure the final hash matches the expected current hash.
-    assert_eq!(
-        previous_hash, current_hash,
-        "last hash doesnt correspond to verified hash"
+}
+
+/// Checks the seals of a Linea block header under the given consensus.
+///
+/// Single-sequencer blocks are checked with [`check_linea_sequencer_seal`] and QBFT blocks with
+/// [`check_qbft_seals`] against the validator set returned by [`get_qbft_validator_set`].
+///
+/// # Arguments
+/// * `consensus` - The consensus that produced the block, see [`get_linea_consensus`].
+/// * `chain_id` - The chain ID (Linea mainnet or Sepolia).
+/// * `header` - The Linea block header to check.
+/// * `eth_env` - Optional validated Ethereum EVM environment, required if the validator set is read from L1.
+///
+/// # Returns
+/// * `Result<(), LineaSealError>` - `Ok` if the block is sealed according to its consensus.
+pub fn check_linea_seals(
+    consensus: &LineaConsensus,
+    chain_id: u64,
+    header: &Header,
+    eth_env: Option<&EvmEnv<StateDb, EthEvmFactory, Commitment>>,
+) -> Result<(), LineaSealError> {
+    match consensus {
+        LineaConsensus::Sequencer => check_linea_sequencer_seal(chain_id, header),
+        LineaConsensus::Qbft { validators } => {
+            let validator_set =
+                get_qbft_validator_set(validators, eth_env).map_err(LineaSealError::Qbft)?;
+            check_qbft_seals(header, &validator_set).map_err(LineaSealError::Qbft)
+        }
+    }
+}
+
+/// Validates a Linea block header by verifying the sequencer signature.
+///
+/// This function checks that the block is signed by the official Linea sequencer, see
+/// [`check_linea_sequencer_seal`].
+///
+/// # Arguments
+/// * `chain_id` - The chain ID (Linea mainnet or Sepolia).
+/// * `block_header_to_validate` - The Linea block header to validate.
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is not a Linea chain.
+/// * No sequencer is scheduled for the block.
+/// * The seal fails [`check_linea_sequencer_seal`].
+pub fn validate_linea_sequencer_seal(chain_id: u64, block_header_to_validate: &RlpHeader<Header>) {
+    if let Err(err) = check_linea_sequencer_seal(chain_id, block_header_to_validate.inner()) {
+        panic!("{err}");
+    }
+}
+
+/// Errors returned when the sequencer seal of a Linea block is invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LineaSealError {
+    /// The extra data fails [`parse_linea_extra_data`].
+    ExtraData(LineaExtraDataError),
+    /// The signer of the seal can't be recovered.
+    InvalidSignature,
+    /// The seal is not signed by the sequencer scheduled for the block.
+    UnexpectedSigner { signer: Address, expected: Address },
+    /// The QBFT seals of the block are invalid.
+    Qbft(QbftError),
+}
+
+impl core::fmt::Display for LineaSealError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ExtraData(err) => write!(f, "{err}"),
+            Self::InvalidSignature => {
+                write!(f, "Failed to recover sequencer address from signature")
+            }
+            Self::UnexpectedSigner { signer, expected } => write!(
+                f,
+                "Block not signed by linea sequencer: {signer} != {expected}"
+            ),
+            Self::Qbft(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for LineaSealError {}
+
+/// Checks the sequencer seal of a Linea block header.
+///
+/// The signature is extracted from the extra data, and the signer recovered over the header without the
+/// signature must be the sequencer scheduled for the block on the given chain.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID (Linea mainnet or Sepolia).
+/// * `header` - The Linea block header to check.
+///
+/// # Returns
+/// * `Result<(), LineaSealError>` - `Ok` if the block is sealed by the scheduled sequencer.
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is not a Linea chain.
+/// * No sequencer is scheduled for the block.
+pub fn check_linea_sequencer_seal(chain_id: u64, header: &Header) -> Result<(), LineaSealError> {
+    assert!(
+        chain_id == LINEA_CHAIN_ID || chain_id == LINEA_SEPOLIA_CHAIN_ID,
+        "invalid chain id"
+    );
+
+    // Parse the extra data into vanity prefix and signature.
+    let extra_data =
+        parse_linea_extra_data(&header.extra_data).map_err(LineaSealError::ExtraData)?;
+
+    let sig = signature_from_bytes(
+        &extra_data.signature[..]
+            .try_into()
+            .expect("Failed to convert signature bytes to fixed array"),
     );
+
+    // Remove the signature from the header for sighash calculation.
+    let mut header = header.clone();
+    header.extra_data = extra_data.vanity;
+    let sighash = header.hash_slow();
+
+    // Recover the sequencer address from the signature and sighash.
+    let sequencer = recover_signer(sig, sighash).ok_or(LineaSealError::InvalidSignature)?;
+
+    // Ensure the recovered sequencer matches the sequencer scheduled for the block.
+    let expected_sequencer = get_expected_sequencer(chain_id, header.number);
+    if sequencer != expected_sequencer {
+        return Err(LineaSealError::UnexpectedSigner {
+            signer: sequencer,
+            expected: expected_sequencer,
+        });
+    }
+    Ok(())
+}
+
+/// Source of the validator set of a Linea QBFT chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QbftValidatorSet {
+    /// A validator set compiled into the guest.
+    Configured(&'static [Address]),
+    /// The validator set returned by `getValidators()` of a contract on L1.
+    L1 { registry: Address },
+}
+
+/// Consensus producing Linea blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineaConsensus {
+    /// A single sequencer seal at the end of the extra data.
+    Sequencer,
+    /// QBFT committed seals from a validator set.
+    Qbft { validators: QbftValidatorSet },
+}
+
+sol! {
+    /// Validator set registry of a QBFT chain on L1.
+    interface IQbftValidatorRegistry {
+        function getValidators() external view returns (address[] memory);
+    }
+}
+
+/// Returns the addresses of a QBFT validator set.
+///
+/// Validator sets read from L1 must be read through an Ethereum environment that is validated independently
+/// of the Linea block, such as the environment used for L1 inclusion.
+///
+/// # Arguments
+/// * `validators` - The source of the validator set.
+/// * `eth_env` - Optional validated Ethereum EVM environment, required for validator sets read from L1.
+///
+/// # Returns
+/// * `Result<Vec<Address>, QbftError>` - The validator set, or [`QbftError::ValidatorSetUnavailable`] if it is
+///   read from L1 without an Ethereum environment, as for proofs without L1 inclusion.
+///
+/// # Panics
+/// Panics if the registry call fails.
+pub fn get_qbft_validator_set(
+    validators: &QbftValidatorSet,
+    eth_env: Option<&EvmEnv<StateDb, EthEvmFactory, Commitment>>,
+) -> Result<Vec<Address>, QbftError> {
+    match validators {
+        QbftValidatorSet::Configured(validators) => Ok(validators.to_vec()),
+        QbftValidatorSet::L1 { registry } => {
+            let eth_env = eth_env.ok_or(QbftError::ValidatorSetUnavailable {
+                registry: *registry,
+            })?;
+            let registry = Contract::new(*registry, eth_env);
+            let call = IQbftValidatorRegistry::getValidatorsCall {};
+            Ok(registry.call_builder(&call).call())
+        }
+    }
+}
+
+/// Consensus used from a given Linea block onwards.
+///
+/// No built-in Linea network runs QBFT yet; its activation is staged by appending an entry here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineaConsensusScheduleEntry {
+    pub consensus: LineaConsensus,
+    /// First block produced under this consensus.
+    pub valid_from_block: u64,
+}
+
+const LINEA_CONSENSUS_SCHEDULE: &[LineaConsensusScheduleEntry] = &[LineaConsensusScheduleEntry {
+    consensus: LineaConsensus::Sequencer,
+    valid_from_block: 0,
+}];
+const LINEA_SEPOLIA_CONSENSUS_SCHEDULE: &[LineaConsensusScheduleEntry] =
+    &[LineaConsensusScheduleEntry {
+        consensus: LineaConsensus::Sequencer,
+        valid_from_block: 0,
+    }];
+
+/// Returns the consensus that produced the given Linea block.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID (Linea mainnet or Sepolia).
+/// * `block_number` - The block number.
+///
+/// # Returns
+/// * `LineaConsensus` - The consensus active at the block.
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is not a Linea chain.
+/// * No consensus is scheduled for the block.
+pub fn get_linea_consensus(chain_id: u64, block_number: u64) -> LineaConsensus {
+    let schedule = match chain_id {
+        LINEA_CHAIN_ID => LINEA_CONSENSUS_SCHEDULE,
+        LINEA_SEPOLIA_CHAIN_ID => LINEA_SEPOLIA_CONSENSUS_SCHEDULE,
+        _ => panic!("invalid chain id"),
+    };
+    find_linea_consensus(schedule, block_number).expect("no consensus scheduled for block")
+}
+
+/// Returns the consensus a Linea consensus schedule assigns to the given block.
+///
+/// # Arguments
+/// * `schedule` - The consensus schedule, in block order.
+/// * `block_number` - The block number.
+///
+/// # Returns
+/// * `Option<LineaConsensus>` - The consensus of the last entry starting at or before the block, if any.
+pub fn find_linea_consensus(
+    schedule: &[LineaConsensusScheduleEntry],
+    block_number: u64,
+) -> Option<LineaConsensus> {
+    schedule
+        .iter()
+        .rev()
+        .find(|entry| entry.valid_from_block <= block_number)
+        .map(|entry| entry.consensus)
+}
+
+/// Computes the hash identifying a block on the given chain.
+///
+/// This is the header hash for all chains except Linea QBFT blocks, whose hash excludes the committed seals
+/// and round number so that it is known before the seals are collected.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID of the block.
+/// * `header` - The block header.
+///
+/// # Returns
+/// * `B256` - The block hash.
+pub fn get_block_hash(chain_id: u64, header: &Header) -> B256 {
+    let is_qbft = (chain_id == LINEA_CHAIN_ID || chain_id == LINEA_SEPOLIA_CHAIN_ID)
+        && matches!(
+            get_linea_consensus(chain_id, header.number),
+            LineaConsensus::Qbft { .. }
+        );
+    if !is_qbft {
+        return header.hash_slow();
+    }
+    let extra_data =
+        decode_qbft_extra_data(&header.extra_data).unwrap_or_else(|err| panic!("{err}"));
+    let mut onchain_header = header.clone();
+    onchain_header.extra_data = extra_data.encode_without_seals(0);
+    onchain_header.hash_slow()
+}
+
+/// Decoded QBFT extra data: `RLP([vanity, validators, vote, round, committed_seals])`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QbftExtraData {
+    pub vanity: Bytes,
+    pub validators: Vec<Address>,
+    pub round: u32,
+    pub committed_seals: Vec<Bytes>,
+    /// Raw RLP of the vanity, validators and vote items, reused when re-encoding without seals.
+    leading_items: Bytes,
+}
+
+impl QbftExtraData {
+    /// Encodes the extra data with the given round and an empty committed seal list.
+    ///
+    /// With the actual round this is the extra data the committed seals sign over; with round zero it is
+    /// the extra data of the on-chain block hash.
+    pub fn encode_without_seals(&self, round: u32) -> Bytes {
+        let round = alloy_rlp::encode(round);
+        let payload_length = self.leading_items.len() + round.len() + 1;
+        let mut out = Vec::with_capacity(payload_length + 9);
+        alloy_rlp::Header {
+            list: true,
+            payload_length,
+        }
+        .encode(&mut out);
+        out.extend_from_slice(&self.leading_items);
+        out.extend_from_slice(&round);
+        out.push(alloy_rlp::EMPTY_LIST_CODE);
+        out.into()
+    }
+}
+
+/// Errors returned when QBFT extra data or committed seals are invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QbftError {
+    /// The extra data is not valid QBFT RLP.
+    Rlp(alloy_rlp::Error),
+    /// The validators in the extra data differ from the expected validator set.
+    ValidatorSetMismatch,
+    /// A committed seal was not produced by a validator.
+    UnknownSigner { signer: Address },
+    /// A validator signed more than one committed seal.
+    DuplicateSigner { signer: Address },
+    /// Fewer distinct validators signed than the quorum requires.
+    InsufficientQuorum { signers: usize, quorum: usize },
+    /// A committed seal is not a 65-byte signature.
+    InvalidSealLength { index: usize, length: usize },
+    /// The signer of a committed seal can't be recovered.
+    InvalidSeal { index: usize },
+    /// The validator set is read from L1, but no Ethereum environment is available.
+    ValidatorSetUnavailable { registry: Address },
+}
+
+impl From<alloy_rlp::Error> for QbftError {
+    fn from(err: alloy_rlp::Error) -> Self {
+        Self::Rlp(err)
+    }
+}
+
+impl core::fmt::Display for QbftError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Rlp(err) => write!(f, "invalid qbft extra data: {err}"),
+            Self::ValidatorSetMismatch => write!(f, "qbft validator set mismatch"),
+            Self::UnknownSigner { signer } => {
+                write!(f, "qbft seal not signed by validator: {signer}")
+            }
+            Self::DuplicateSigner { signer } => write!(f, "duplicate qbft seal from {signer}"),
+            Self::InsufficientQuorum { signers, quorum } => {
+                write!(f, "insufficient qbft quorum: {signers} < {quorum}")
+            }
+            Self::InvalidSealLength { index, length } => {
+                write!(f, "invalid qbft seal length at index {index}: {length}")
+            }
+            Self::InvalidSeal { index } => write!(f, "invalid qbft seal at index {index}"),
+            Self::ValidatorSetUnavailable { registry } => write!(
+                f,
+                "qbft validator set on l1 at {registry} requires an ethereum environment"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QbftError {}
+
+/// Decodes QBFT extra data.
+///
+/// # Arguments
+/// * `extra_data` - The extra data of a QBFT header.
+///
+/// # Returns
+/// * `Result<QbftExtraData, QbftError>` - The decoded extra data.
+pub fn decode_qbft_extra_data(extra_data: &[u8]) -> Result<QbftExtraData, QbftError> {
+    use alloy_rlp::Decodable;
+
+    let mut buf = extra_data;
+    let header = alloy_rlp::Header::decode(&mut buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString.into());
+    }
+    if buf.len() != header.payload_length {
+        return Err(alloy_rlp::Error::UnexpectedLength.into());
+    }
+    let items = buf;
+
+    let vanity = Bytes::decode(&mut buf)?;
+    let validators = Vec::<Address>::decode(&mut buf)?;
+    // The vote is either an empty list or `[recipient, vote_type]`; only its raw encoding is needed.
+    let vote = alloy_rlp::Header::decode(&mut buf)?;
+    if !vote.list || buf.len() < vote.payload_length {
+        return Err(alloy_rlp::Error::UnexpectedString.into());
+    }
+    buf = &buf[vote.payload_length..];
+    let leading_items = Bytes::copy_from_slice(&items[..items.len() - buf.len()]);
+
+    let round = u32::decode(&mut buf)?;
+    let committed_seals = Vec::<Bytes>::decode(&mut buf)?;
+    if !buf.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength.into());
+    }
+
+    Ok(QbftExtraData {
+        vanity,
+        validators,
+        round,
+        committed_seals,
+        leading_items,
+    })
+}
+
+/// Returns the number of validators required for a QBFT quorum, `ceil(2n / 3)`.
+pub fn qbft_quorum(validator_count: usize) -> usize {
+    (2 * validator_count).div_ceil(3)
+}
+
+/// Validates a Linea QBFT block header by verifying its committed seals.
+///
+/// The validators in the extra data must equal the expected validator set, which is either configured per
+/// chain or read from L1 by the caller, and a quorum of distinct validators must have signed committed seals
+/// over the header without seals.
+///
+/// # Arguments
+/// * `block_header_to_validate` - The Linea block header to validate.
+/// * `validator_set` - The expected validator set.
+///
+/// # Panics
+/// Panics if:
+/// * Extra data is not valid QBFT extra data.
+/// * The validators in the extra data differ from the expected set.
+/// * A seal is malformed, can't be recovered, is not from a validator, or is duplicated.
+/// * Fewer validators signed than the quorum requires.
+pub fn validate_linea_qbft_seals(
+    block_header_to_validate: &RlpHeader<Header>,
+    validator_set: &[Address],
+) {
+    if let Err(err) = check_qbft_seals(block_header_to_validate.inner(), validator_set) {
+        panic!("{err}");
+    }
+}
+
+/// Checks the committed seals of a QBFT header against a validator set.
+///
+/// See [`validate_linea_qbft_seals`].
+pub fn check_qbft_seals(header: &Header, validator_set: &[Address]) -> Result<(), QbftError> {
+    let extra_data = decode_qbft_extra_data(&header.extra_data)?;
+
+    let mut validators = extra_data.validators.clone();
+    validators.sort_unstable();
+    let mut expected_validators = validator_set.to_vec();
+    expected_validators.sort_unstable();
+    if validators != expected_validators {
+        return Err(QbftError::ValidatorSetMismatch);
+    }
+
+    // Committed seals sign the header with the round but without any seals.
+    let mut seal_header = header.clone();
+    seal_header.extra_data = extra_data.encode_without_seals(extra_data.round);
+    let sighash = seal_header.hash_slow();
+
+    let mut signers = Vec::with_capacity(extra_data.committed_seals.len());
+    for (index, seal) in extra_data.committed_seals.iter().enumerate() {
+        let seal_bytes: &[u8; 65] =
+            seal[..]
+                .try_into()
+                .map_err(|_| QbftError::InvalidSealLength {
+                    index,
+                    length: seal.len(),
+                })?;
+        let sig = signature_from_bytes(seal_bytes);
+        let signer = recover_signer(sig, sighash).ok_or(QbftError::InvalidSeal { index })?;
+        if !validators.contains(&signer) {
+            return Err(QbftError::UnknownSigner { signer });
+        }
+        if signers.contains(&signer) {
+            return Err(QbftError::DuplicateSigner { signer });
+        }
+        signers.push(signer);
+    }
+
+    let quorum = qbft_quorum(validators.len());
+    if signers.len() < quorum {
+        return Err(QbftError::InsufficientQuorum {
+            signers: signers.len(),
+            quorum,
+        });
+    }
+    Ok(())
+}
+
+/// Activation timestamps of the OpStack hard forks that change the gossiped payload format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpStackHardforks {
+    /// Canyon: payloads carry withdrawals (V2).
+    pub canyon_time: u64,
+    /// Ecotone: payloads are wrapped in an envelope with the parent beacon block root (V3).
+    pub ecotone_time: u64,
+    /// Isthmus: payloads carry the L2ToL1MessagePasser withdrawals root (V4).
+    pub isthmus_time: u64,
+}
+
+/// Returns the payload-relevant hard fork timestamps of the given OpStack chain.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+///
+/// # Returns
+/// * `OpStackHardforks` - The hard fork activation timestamps.
+///
+/// # Panics
+/// Panics if the chain ID is invalid or unsupported.
+pub fn get_opstack_hardforks(chain_id: u64) -> OpStackHardforks {
+    match chain_id {
+        OPTIMISM_CHAIN_ID | BASE_CHAIN_ID => OpStackHardforks {
+            canyon_time: 1_704_992_401,
+            ecotone_time: 1_710_374_401,
+            isthmus_time: 1_746_806_401,
+        },
+        OPTIMISM_SEPOLIA_CHAIN_ID | BASE_SEPOLIA_CHAIN_ID => OpStackHardforks {
+            canyon_time: 1_699_981_200,
+            ecotone_time: 1_708_534_800,
+            isthmus_time: 1_744_905_600,
+        },
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Parameters of an OpStack chain that is not built in, in the format of the op-node `rollup.json`.
+///
+/// Fields of `rollup.json` that validation doesn't need are ignored. The unsafe block signer and the reorg
+/// protection depth are not part of `rollup.json` and must be added to it. Since the config is supplied by the
+/// prover, its [`OpStackRollupConfig::config_hash`] is committed to the journal; mErc20Host and mTokenGateway
+/// only accept journals whose hash governance added to their `allowedChainConfigs`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpStackRollupConfig {
+    pub l1_chain_id: u64,
+    pub l2_chain_id: u64,
+    /// OptimismPortal of the chain on L1.
+    pub deposit_contract_address: Address,
+    /// SystemConfig of the chain on L1.
+    pub l1_system_config_address: Address,
+    /// Canyon activation timestamp, `None` if not scheduled.
+    #[serde(default)]
+    pub canyon_time: Option<u64>,
+    /// Ecotone activation timestamp, `None` if not scheduled.
+    #[serde(default)]
+    pub ecotone_time: Option<u64>,
+    /// Isthmus activation timestamp, `None` if not scheduled.
+    #[serde(default)]
+    pub isthmus_time: Option<u64>,
+    /// Key signing gossiped payloads, as announced by the SystemConfig.
+    pub unsafe_block_signer: Address,
+    /// Linking blocks required when the chain is validated through its sequencer.
+    pub reorg_protection_depth: u64,
+}
+
+impl OpStackRollupConfig {
+    /// Returns the payload-relevant hard fork timestamps; unscheduled forks never activate.
+    pub fn hardforks(&self) -> OpStackHardforks {
+        OpStackHardforks {
+            canyon_time: self.canyon_time.unwrap_or(u64::MAX),
+            ecotone_time: self.ecotone_time.unwrap_or(u64::MAX),
+            isthmus_time: self.isthmus_time.unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Returns the dispute game policy, accepting the standard OpStack game types.
+    pub fn dispute_game_policy(&self) -> DisputeGamePolicy {
+        DisputeGamePolicy {
+            accepted_game_types: &[
+                GAME_TYPE_CANNON,
+                GAME_TYPE_PERMISSIONED_CANNON,
+                GAME_TYPE_SUPER_CANNON,
+                GAME_TYPE_SUPER_PERMISSIONED_CANNON,
+            ],
+            proof_maturity_margin_seconds: DEFAULT_PROOF_MATURITY_MARGIN_SECONDS,
+            strict_proof_maturity: false,
+            require_respected_when_created: true,
+        }
+    }
+
+    /// Returns the reorg protection policy when the chain is validated through its sequencer.
+    pub fn reorg_protection_policy(&self) -> ReorgProtectionPolicy {
+        ReorgProtectionPolicy::blocks(self.reorg_protection_depth)
+    }
+
+    /// Computes the hash committed to the journal:
+    /// `keccak256(abi.encode(l1ChainId, l2ChainId, portal, systemConfig, canyon, ecotone, isthmus, signer, depth))`.
+    pub fn config_hash(&self) -> B256 {
+        let hardforks = self.hardforks();
+        keccak256(
+            (
+                self.l1_chain_id,
+                self.l2_chain_id,
+                self.deposit_contract_address,
+                self.l1_system_config_address,
+                hardforks.canyon_time,
+                hardforks.ecotone_time,
+                hardforks.isthmus_time,
+                self.unsafe_block_signer,
+                self.reorg_protection_depth,
+            )
+                .abi_encode(),
+        )
+    }
+}
+
+/// Returns whether the chain is validated with built-in parameters.
+pub fn is_builtin_chain(chain_id: u64) -> bool {
+    matches!(
+        chain_id,
+        ETHEREUM_CHAIN_ID
+            | ETHEREUM_SEPOLIA_CHAIN_ID
+            | OPTIMISM_CHAIN_ID
+            | OPTIMISM_SEPOLIA_CHAIN_ID
+            | BASE_CHAIN_ID
+            | BASE_SEPOLIA_CHAIN_ID
+            | LINEA_CHAIN_ID
+            | LINEA_SEPOLIA_CHAIN_ID
+            | SCROLL_CHAIN_ID
+            | SCROLL_SEPOLIA_CHAIN_ID
+    )
+}
+
+/// Returns the L1 chain ID of an OpStack chain, built in or configured.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+/// * `opstack_rollup_config` - Optional rollup config of a configured chain.
+///
+/// # Returns
+/// * `u64` - The L1 chain ID.
+///
+/// # Panics
+/// Panics if the chain is neither built in nor configured.
+pub fn get_opstack_l1_chain_id(
+    chain_id: u64,
+    opstack_rollup_config: Option<&OpStackRollupConfig>,
+) -> u64 {
+    match (chain_id, opstack_rollup_config) {
+        (_, Some(rollup_config)) => rollup_config.l1_chain_id,
+        (OPTIMISM_CHAIN_ID | BASE_CHAIN_ID, None) => ETHEREUM_CHAIN_ID,
+        (OPTIMISM_SEPOLIA_CHAIN_ID | BASE_SEPOLIA_CHAIN_ID, None) => ETHEREUM_SEPOLIA_CHAIN_ID,
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Returns the L1 chain ID a built-in L2 settles on.
+///
+/// # Arguments
+/// * `chain_id` - The L2 chain ID.
+///
+/// # Returns
+/// * `u64` - The Ethereum chain ID (mainnet or Sepolia).
+///
+/// # Panics
+/// Panics if the chain ID is not a built-in L2.
+pub fn get_l1_chain_id(chain_id: u64) -> u64 {
+    match chain_id {
+        OPTIMISM_CHAIN_ID | BASE_CHAIN_ID | LINEA_CHAIN_ID | SCROLL_CHAIN_ID => ETHEREUM_CHAIN_ID,
+        OPTIMISM_SEPOLIA_CHAIN_ID
+        | BASE_SEPOLIA_CHAIN_ID
+        | LINEA_SEPOLIA_CHAIN_ID
+        | SCROLL_SEPOLIA_CHAIN_ID => ETHEREUM_SEPOLIA_CHAIN_ID,
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Block from which Linea Sepolia runs London rules (genesis).
+pub const LINEA_SEPOLIA_LONDON_BLOCK: u64 = 0;
+/// Timestamp from which Linea Sepolia runs Prague rules.
+pub const LINEA_SEPOLIA_PRAGUE_TIMESTAMP: u64 = 1_753_106_400;
+
+/// Linea Sepolia chain spec, with the fork activations of the testnet.
+pub static LINEA_SEPOLIA_CHAIN_SPEC: LazyLock<EthChainSpec> = LazyLock::new(|| EthChainSpec {
+    chain_id: LINEA_SEPOLIA_CHAIN_ID,
+    forks: BTreeMap::from([
+        (
+            SpecId::LONDON,
+            ForkCondition::Block(LINEA_SEPOLIA_LONDON_BLOCK),
+        ),
+        (
+            SpecId::PRAGUE,
+            ForkCondition::Timestamp(LINEA_SEPOLIA_PRAGUE_TIMESTAMP),
+        ),
+    ]),
+});
+
+/// Returns the chain spec used to execute view calls on an Ethereum-style chain.
+///
+/// OpStack chains validated through their sequencer and Scroll use the Ethereum spec of the
+/// network they settle on.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID of the environment.
+///
+/// # Returns
+/// * `&'static EthChainSpec` - The chain spec with the fork activations of the chain.
+///
+/// # Panics
+/// Panics if the chain ID is invalid or unsupported.
+pub fn get_eth_chain_spec(chain_id: u64) -> &'static EthChainSpec {
+    match chain_id {
+        ETHEREUM_CHAIN_ID | OPTIMISM_CHAIN_ID | BASE_CHAIN_ID | SCROLL_CHAIN_ID => {
+            &ETH_MAINNET_CHAIN_SPEC
+        }
+        ETHEREUM_SEPOLIA_CHAIN_ID
+        | OPTIMISM_SEPOLIA_CHAIN_ID
+        | BASE_SEPOLIA_CHAIN_ID
+        | SCROLL_SEPOLIA_CHAIN_ID => &ETH_SEPOLIA_CHAIN_SPEC,
+        LINEA_CHAIN_ID => &LINEA_MAINNET_CHAIN_SPEC,
+        LINEA_SEPOLIA_CHAIN_ID => &LINEA_SEPOLIA_CHAIN_SPEC,
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Returns the chain spec used to execute view calls on an OpStack chain.
+///
+/// Superchain members share the hard fork activations of Optimism on the same network, so Base and configured
+/// chains use the Optimism spec of their network.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+/// * `opstack_rollup_config` - Optional rollup config of a configured chain.
+///
+/// # Returns
+/// * `&'static OpChainSpec` - The chain spec with the fork activations of the chain.
+///
+/// # Panics
+/// Panics if the chain is neither built in nor configured.
+pub fn get_op_chain_spec(
+    chain_id: u64,
+    opstack_rollup_config: Option<&OpStackRollupConfig>,
+) -> &'static OpChainSpec {
+    match get_opstack_l1_chain_id(chain_id, opstack_rollup_config) {
+        ETHEREUM_CHAIN_ID => &OP_MAINNET_CHAIN_SPEC,
+        ETHEREUM_SEPOLIA_CHAIN_ID => &OP_SEPOLIA_CHAIN_SPEC,
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Validates an OpStack environment of a configured chain through its sequencer commitment.
+///
+/// Counterpart of [`validate_opstack_env`] using the unsafe block signer and hard forks of the rollup config.
+///
+/// # Arguments
+/// * `rollup_config` - The rollup config of the chain.
+/// * `commitment` - The sequencer commitment to verify.
+/// * `env_block_hash` - The block hash to validate against.
+///
+/// # Panics
+/// Panics if:
+/// * Commitment verification fails.
+/// * Block hash doesn't match commitment.
+/// * The payload can't be decoded or its version doesn't match the active hard fork.
+pub fn validate_opstack_env_with_rollup_config(
+    rollup_config: &OpStackRollupConfig,
+    commitment: &SequencerCommitment,
+    env_block_hash: B256,
+) {
+    if let Err(err) = check_sequencer_commitment(
+        rollup_config.l2_chain_id,
+        &rollup_config.hardforks(),
+        rollup_config.unsafe_block_signer,
+        commitment,
+        env_block_hash,
+    ) {
+        panic!("{err}");
+    }
+}
+
+/// Version of a gossiped OpStack execution payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpStackPayloadVersion {
+    /// Bedrock `ExecutionPayloadV1`.
+    V1,
+    /// Canyon `ExecutionPayloadV2`, adding withdrawals.
+    V2,
+    /// Ecotone envelope with parent beacon block root around `ExecutionPayloadV3`.
+    V3,
+    /// Isthmus envelope around `ExecutionPayloadV4`, adding the withdrawals root.
+    V4,
+}
+
+impl OpStackPayloadVersion {
+    /// Returns the payload version required by the hard forks active at `timestamp`.
+    pub fn at_timestamp(hardforks: &OpStackHardforks, timestamp: u64) -> Self {
+        if timestamp >= hardforks.isthmus_time {
+            Self::V4
+        } else if timestamp >= hardforks.ecotone_time {
+            Self::V3
+        } else if timestamp >= hardforks.canyon_time {
+            Self::V2
+        } else {
+            Self::V1
+        }
+    }
+
+    /// Length of the envelope prefix (parent beacon block root) before the SSZ payload.
+    pub fn envelope_prefix_len(self) -> usize {
+        match self {
+            Self::V1 | Self::V2 => 0,
+            Self::V3 | Self::V4 => 32,
+        }
+    }
+
+    /// Length of the fixed-size part of the SSZ payload.
+    pub fn fixed_len(self) -> usize {
+        match self {
+            Self::V1 => 508,
+            Self::V2 => 512,
+            Self::V3 => 528,
+            Self::V4 => 560,
+        }
+    }
+}
+
+/// Header fields of a gossiped OpStack execution payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpStackPayloadHeader {
+    pub version: OpStackPayloadVersion,
+    /// Parent beacon block root from the envelope (V3 and later).
+    pub parent_beacon_block_root: Option<B256>,
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub block_hash: B256,
+    /// L2ToL1MessagePasser storage root (V4 and later).
+    pub withdrawals_root: Option<B256>,
+}
+
+/// Errors returned when a gossiped OpStack payload can't be decoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OpStackPayloadError {
+    /// The data matches none of the known payload layouts.
+    UnknownLayout { length: usize },
+    /// The payload version doesn't match the hard fork active at the payload timestamp.
+    ForkMismatch {
+        version: OpStackPayloadVersion,
+        expected: OpStackPayloadVersion,
+    },
+}
+
+impl core::fmt::Display for OpStackPayloadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownLayout { length } => {
+                write!(f, "unknown payload layout for {length} bytes")
+            }
+            Self::ForkMismatch { version, expected } => write!(
+                f,
+                "payload version {version:?} doesn't match active hard fork version {expected:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OpStackPayloadError {}
+
+/// Offset of `block_number` in the SSZ execution payload.
+const PAYLOAD_BLOCK_NUMBER_OFFSET: usize = 404;
+/// Offset of `timestamp` in the SSZ execution payload.
+const PAYLOAD_TIMESTAMP_OFFSET: usize = 428;
+/// Offset of the `extra_data` offset field in the SSZ execution payload.
+const PAYLOAD_EXTRA_DATA_OFFSET: usize = 436;
+/// Offset of `block_hash` in the SSZ execution payload.
+const PAYLOAD_BLOCK_HASH_OFFSET: usize = 472;
+/// Offset of `withdrawals_root` in the SSZ `ExecutionPayloadV4`.
+const PAYLOAD_WITHDRAWALS_ROOT_OFFSET: usize = 528;
+
+/// Decodes the header fields of `data` assuming the given payload version.
+///
+/// The variable-size fields of an SSZ payload start right after its fixed-size part, so the
+/// `extra_data` offset identifies the layout.
+fn decode_opstack_payload_as(
+    version: OpStackPayloadVersion,
+    data: &[u8],
+) -> Option<OpStackPayloadHeader> {
+    let prefix_len = version.envelope_prefix_len();
+    let payload = data.get(prefix_len..)?;
+    if payload.len() < version.fixed_len() {
+        return None;
+    }
+    let read_u64 = |offset: usize| {
+        u64::from_le_bytes(payload[offset..offset + 8].try_into().unwrap())
+    };
+    let extra_data_offset = u32::from_le_bytes(
+        payload[PAYLOAD_EXTRA_DATA_OFFSET..PAYLOAD_EXTRA_DATA_OFFSET + 4]
+            .try_into()
+            .unwrap(),
+    );
+    if extra_data_offset as usize != version.fixed_len() {
+        return None;
+    }
+    Some(OpStackPayloadHeader {
+        version,
+        parent_beacon_block_root: (prefix_len > 0).then(|| B256::from_slice(&data[..32])),
+        block_number: read_u64(PAYLOAD_BLOCK_NUMBER_OFFSET),
+        timestamp: read_u64(PAYLOAD_TIMESTAMP_OFFSET),
+        block_hash: B256::from_slice(
+            &payload[PAYLOAD_BLOCK_HASH_OFFSET..PAYLOAD_BLOCK_HASH_OFFSET + 32],
+        ),
+        withdrawals_root: (version == OpStackPayloadVersion::V4).then(|| {
+            B256::from_slice(
+                &payload[PAYLOAD_WITHDRAWALS_ROOT_OFFSET..PAYLOAD_WITHDRAWALS_ROOT_OFFSET + 32],
+            )
+        }),
+    })
+}
+
+/// Decodes the header fields of a gossiped OpStack payload of any supported version.
+///
+/// The payload version is detected from the SSZ layout and must match the version required by the
+/// hard forks of the chain at the payload timestamp. The sequencer signature covers the full gossiped
+/// data (including the V3/V4 envelope), so it is unaffected by the version.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+/// * `data` - The signed payload data of a sequencer commitment.
+///
+/// # Returns
+/// * `Result<OpStackPayloadHeader, OpStackPayloadError>` - The decoded header fields.
+///
+/// # Panics
+/// Panics if the chain ID is invalid or unsupported.
+pub fn decode_opstack_payload(
+    chain_id: u64,
+    data: &[u8],
+) -> Result<OpStackPayloadHeader, OpStackPayloadError> {
+    decode_opstack_payload_with_hardforks(&get_opstack_hardforks(chain_id), data)
+}
+
+/// Decodes the header fields of a gossiped OpStack payload against the given hard fork schedule.
+///
+/// See [`decode_opstack_payload`].
+///
+/// # Arguments
+/// * `hardforks` - The hard fork activation timestamps of the chain.
+/// * `data` - The signed payload data of a sequencer commitment.
+///
+/// # Returns
+/// * `Result<OpStackPayloadHeader, OpStackPayloadError>` - The decoded header fields.
+pub fn decode_opstack_payload_with_hardforks(
+    hardforks: &OpStackHardforks,
+    data: &[u8],
+) -> Result<OpStackPayloadHeader, OpStackPayloadError> {
+    let candidates = [
+        OpStackPayloadVersion::V1,
+        OpStackPayloadVersion::V2,
+        OpStackPayloadVersion::V3,
+        OpStackPayloadVersion::V4,
+    ]
+    .into_iter()
+    .filter_map(|version| decode_opstack_payload_as(version, data));
+
+    // Envelope and plain layouts could both parse in theory; the active hard fork disambiguates.
+    let mut mismatch = None;
+    for header in candidates {
+        let expected = OpStackPayloadVersion::at_timestamp(hardforks, header.timestamp);
+        if header.version == expected {
+            return Ok(header);
+        }
+        mismatch.get_or_insert(OpStackPayloadError::ForkMismatch {
+            version: header.version,
+            expected,
+        });
+    }
+    Err(mismatch.unwrap_or(OpStackPayloadError::UnknownLayout { length: data.len() }))
+}
+
+/// Validates an OpStack (Optimism/Base) environment through sequencer commitments.
+///
+/// This function verifies the sequencer commitment for OpStack chains, checks the signature against the sequencer
+/// scheduled for the payload's block, and ensures the block hash matches.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID (Optimism or Base, mainnet or Sepolia).
+/// * `commitment` - The sequencer commitment to verify.
+/// * `env_block_hash` - The block hash to validate against.
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is not an OpStack chain.
+/// * No sequencer is scheduled for the payload's block.
+/// * Commitment verification fails.
+/// * Block hash doesn't match commitment.
+/// * Sequencer signature is invalid.
+/// * The payload can't be decoded or its version doesn't match the active hard fork.
+pub fn validate_opstack_env(chain_id: u64, commitment: &SequencerCommitment, env_block_hash: B256) {
+    // Decode the commitment payload to find the sequencer scheduled for its block.
+    let hardforks = get_opstack_hardforks(chain_id);
+    let payload = decode_opstack_payload_with_hardforks(&hardforks, &commitment.data)
+        .expect("Failed to decode sequencer commitment payload");
+    let expected_sequencer = get_expected_sequencer(chain_id, payload.block_number);
+
+    // Verify the sequencer commitment for the correct chain and sequencer address, and check the block hash.
+    if let Err(err) = check_sequencer_commitment(
+        chain_id,
+        &hardforks,
+        expected_sequencer,
+        commitment,
+        env_block_hash,
+    ) {
+        panic!("{err}");
+    }
+}
+
+/// Errors returned when a sequencer commitment doesn't vouch for a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SequencerCommitmentError {
+    /// The commitment is not signed by the expected sequencer for the chain.
+    InvalidSignature { sequencer: Address, message: String },
+    /// The signed payload can't be decoded.
+    Payload(OpStackPayloadError),
+    /// The payload is for a different block.
+    BlockHashMismatch { expected: B256, found: B256 },
+}
+
+impl core::fmt::Display for SequencerCommitmentError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidSignature { sequencer, message } => write!(
+                f,
+                "sequencer commitment not signed by {sequencer}: {message}"
+            ),
+            Self::Payload(err) => write!(f, "invalid sequencer commitment payload: {err}"),
+            Self::BlockHashMismatch { expected, found } => {
+                write!(f, "block hash mismatch: {found} != {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SequencerCommitmentError {}
+
+/// Checks that a sequencer commitment is signed by the given sequencer and vouches for a block.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID the commitment is signed for.
+/// * `hardforks` - The hard fork activation timestamps of the chain.
+/// * `sequencer` - The sequencer expected to sign the commitment.
+/// * `commitment` - The sequencer commitment to verify.
+/// * `env_block_hash` - The block hash the payload must carry.
+///
+/// # Returns
+/// * `Result<OpStackPayloadHeader, SequencerCommitmentError>` - The header fields of the signed payload.
+pub fn check_sequencer_commitment(
+    chain_id: u64,
+    hardforks: &OpStackHardforks,
+    sequencer: Address,
+    commitment: &SequencerCommitment,
+    env_block_hash: B256,
+) -> Result<OpStackPayloadHeader, SequencerCommitmentError> {
+    commitment.verify(sequencer, chain_id).map_err(|err| {
+        SequencerCommitmentError::InvalidSignature {
+            sequencer,
+            message: err.to_string(),
+        }
+    })?;
+    let payload = decode_opstack_payload_with_hardforks(hardforks, &commitment.data)
+        .map_err(SequencerCommitmentError::Payload)?;
+    if payload.block_hash != env_block_hash {
+        return Err(SequencerCommitmentError::BlockHashMismatch {
+            expected: env_block_hash,
+            found: payload.block_hash,
+        });
+    }
+    Ok(payload)
+}
+
+/// SystemConfig contract of Optimism mainnet on Ethereum.
+pub const OPTIMISM_SYSTEM_CONFIG: Address = address!("229047fed2591dbec1ef1118d64f7af3db9eb290");
+/// SystemConfig contract of Base mainnet on Ethereum.
+pub const BASE_SYSTEM_CONFIG: Address = address!("73a79fab69143498ed3712e519a88a918e1f4072");
+/// SystemConfig contract of Optimism Sepolia on Ethereum Sepolia.
+pub const OPTIMISM_SEPOLIA_SYSTEM_CONFIG: Address =
+    address!("034edd2a225f7f429a63e0f1d2084b9e0a93b538");
+/// SystemConfig contract of Base Sepolia on Ethereum Sepolia.
+pub const BASE_SEPOLIA_SYSTEM_CONFIG: Address =
+    address!("f272670eb55e895584501d564afeb048bed26194");
+
+sol! {
+    /// SystemConfig getters of an OpStack chain.
+    interface ISystemConfig {
+        function unsafeBlockSigner() external view returns (address);
+    }
+}
+
+/// Reads the authorised unsafe block signer of an OpStack chain from its SystemConfig on L1.
+///
+/// The Ethereum environment must be validated independently of the sequencer being looked up,
+/// otherwise the signer would vouch for itself.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID (Optimism or Base, mainnet or Sepolia).
+/// * `eth_env` - A validated Ethereum EVM environment.
+///
+/// # Returns
+/// * `Address` - The unsafe block signer announced on L1.
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is not an OpStack chain.
+/// * The SystemConfig call fails.
+pub fn get_unsafe_block_signer_from_l1(
+    chain_id: u64,
+    eth_env: &EvmEnv<StateDb, EthEvmFactory, Commitment>,
+) -> Address {
+    let system_config_address = match chain_id {
+        OPTIMISM_CHAIN_ID => OPTIMISM_SYSTEM_CONFIG,
+        BASE_CHAIN_ID => BASE_SYSTEM_CONFIG,
+        OPTIMISM_SEPOLIA_CHAIN_ID => OPTIMISM_SEPOLIA_SYSTEM_CONFIG,
+        BASE_SEPOLIA_CHAIN_ID => BASE_SEPOLIA_SYSTEM_CONFIG,
+        _ => panic!("invalid chain id"),
+    };
+    let system_config = Contract::new(system_config_address, eth_env);
+    let call = ISystemConfig::unsafeBlockSignerCall {};
+    system_config.call_builder(&call).call()
+}
+
+/// Validates an OpStack environment against the unsafe block signer read from L1.
+///
+/// Alternative to [`validate_opstack_env`] when a validated Ethereum environment is available: instead of the
+/// sequencer schedule compiled into the guest, the signer is read from the chain's SystemConfig, so a key
+/// rotation announced on L1 is honoured automatically.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID (Optimism or Base, mainnet or Sepolia).
+/// * `commitment` - The sequencer commitment to verify.
+/// * `env_block_hash` - The block hash to validate against.
+/// * `eth_env` - A validated Ethereum EVM environment.
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is not an OpStack chain.
+/// * The SystemConfig call fails.
+/// * Commitment verification fails.
+/// * Block hash doesn't match commitment.
+/// * The payload can't be decoded or its version doesn't match the active hard fork.
+pub fn validate_opstack_env_with_l1_signer(
+    chain_id: u64,
+    commitment: &SequencerCommitment,
+    env_block_hash: B256,
+    eth_env: &EvmEnv<StateDb, EthEvmFactory, Commitment>,
+) {
+    // Verify the sequencer commitment against the signer announced on L1.
+    let unsafe_block_signer = get_unsafe_block_signer_from_l1(chain_id, eth_env);
+    if let Err(err) = check_sequencer_commitment(
+        chain_id,
+        &get_opstack_hardforks(chain_id),
+        unsafe_block_signer,
+        commitment,
+        env_block_hash,
+    ) {
+        panic!("{err}");
+    }
+}
+
+/// Retrieves and validates Ethereum L1 block hash through OpStack L2.
+///
+/// Uses Optimism's L1Block contract to fetch and verify the L1 block hash.
+/// This provides a secure way to verify L1 block hashes through L2 commitments.
+///
+/// # Arguments
+/// * `sequencer_commitment_opstack_1` - The Optimism sequencer commitment.
+/// * `env_input_opstack_for_l1_block_call_1` - The Optimism EVM input containing environment data.
+/// * `chain_id` - The Ethereum chain ID (mainnet or Sepolia).
+/// * `_sequencer_commitment_opstack_2` - (Unused) Optional second sequencer commitment.
+/// * `_env_input_opstack_for_l1_block_call_2` - (Unused) Optional second Optimism EVM input.
+///
+/// # Returns
+/// * `B256` - The validated Ethereum block hash.
+///
+/// # Panics
+/// Panics if:
+/// * OpStack environment validation fails.
+/// * L1Block contract call fails.
+/// * Chain ID is not an Ethereum chain.
+pub fn get_validated_ethereum_block_hash_via_opstack(
+    sequencer_commitment_opstack_1: Option<&SequencerCommitment>,
+    env_input_opstack_for_l1_block_call_1: Option<EthEvmInput>,
+    chain_id: u64,
+    _sequencer_commitment_opstack_2: Option<&SequencerCommitment>,
+    _env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+) -> B256 {
+    // Convert the provided EVM input to an environment.
+    let env_op = env_input_opstack_for_l1_block_call_1
+        .expect("env_input_opstack_for_l1_block_call_1 is None")
+        .into_env(get_eth_chain_spec(chain_id));
+
+    // Determine which OpStack chain to use for validation.
+    let (verify_via_chain_1, _verify_via_chain_2) = if chain_id == ETHEREUM_CHAIN_ID {
+        (OPTIMISM_CHAIN_ID, BASE_CHAIN_ID)
+    } else {
+        (OPTIMISM_SEPOLIA_CHAIN_ID, BASE_SEPOLIA_CHAIN_ID)
+    };
+
+    // Validate the OpStack environment and commitment.
+    validate_opstack_env(
+        verify_via_chain_1,
+        sequencer_commitment_opstack_1.unwrap(),
+        env_op.commitment().digest,
+    );
+
+    // Query the L1 block hash from the L1Block contract.
+    let l1_block = Contract::new(L1_BLOCK_ADDRESS_OPSTACK, &env_op);
+    let call = IL1Block::hashCall {};
+    let l1_hash_1 = l1_block.call_builder(&call).call();
+
+    // (Optional) Could validate via a second chain, but currently omitted.
+    // let env_op_2 = env_input_opstack_for_l1_block_call_2.expect("env_input_opstack_for_l1_block_call_2 is None").into_env();
+    // validate_opstack_env(verify_via_chain_2, sequencer_commitment_opstack_2.unwrap(), env_op_2.commitment().digest);
+    // let l1_block = Contract::new(L1_BLOCK_ADDRESS_OPSTACK, &env_op_2);
+    // let call = IL1Block::hashCall {};
+    // let l1_hash_2 = l1_block.call_builder(&call).call().0;
+    // assert_eq!(l1_hash_1, l1_hash_2, "L1 hash 1 and 2 mismatch");
+
+    l1_hash_1
+}
+
+/// Upper bound on the number of linking blocks accepted for any chain.
+///
+/// Bounds the cycles spent on hashing linking blocks so oversized inputs are rejected up front.
+pub const MAX_LINKING_BLOCKS: u64 = 1024;
+
+/// Reorg protection requirements for the linking blocks of a chain.
+///
+/// Block counts give very different wall-clock guarantees depending on the block time of a chain,
+/// so a policy can additionally (or instead) require the linking chain to span a minimum number of
+/// seconds, measured between the historical block and the last linking block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReorgProtectionPolicy {
+    /// Minimum number of linking blocks, if enforced.
+    pub min_blocks: Option<u64>,
+    /// Minimum seconds between the historical block and the last linking block, if enforced.
+    pub min_elapsed_seconds: Option<u64>,
+    /// Maximum number of linking blocks.
+    pub max_blocks: u64,
+}
+
+impl ReorgProtectionPolicy {
+    /// Requires at least `min_blocks` linking blocks.
+    pub const fn blocks(min_blocks: u64) -> Self {
+        Self {
+            min_blocks: Some(min_blocks),
+            min_elapsed_seconds: None,
+            max_blocks: MAX_LINKING_BLOCKS,
+        }
+    }
+
+    /// Requires the linking blocks to span at least `min_elapsed_seconds`.
+    pub const fn elapsed(min_elapsed_seconds: u64) -> Self {
+        Self {
+            min_blocks: None,
+            min_elapsed_seconds: Some(min_elapsed_seconds),
+            max_blocks: MAX_LINKING_BLOCKS,
+        }
+    }
+
+    /// Requires both at least `min_blocks` linking blocks and a span of `min_elapsed_seconds`.
+    pub const fn blocks_and_elapsed(min_blocks: u64, min_elapsed_seconds: u64) -> Self {
+        Self {
+            min_blocks: Some(min_blocks),
+            min_elapsed_seconds: Some(min_elapsed_seconds),
+            max_blocks: MAX_LINKING_BLOCKS,
+        }
+    }
+
+    /// Caps the number of linking blocks at `max_blocks`.
+    pub const fn with_max_blocks(self, max_blocks: u64) -> Self {
+        Self { max_blocks, ..self }
+    }
+}
+
+/// Nominal block time of the built-in OpStack chains, in seconds.
+pub const OPSTACK_BLOCK_TIME_SECONDS: u64 = 2;
+/// Nominal block time of Linea, in seconds.
+pub const LINEA_BLOCK_TIME_SECONDS: u64 = 2;
+/// Slot time of Ethereum, in seconds.
+pub const ETHEREUM_BLOCK_TIME_SECONDS: u64 = 12;
+
+/// Requires `depth` linking blocks that also span `depth` nominal block times.
+///
+/// Sequencers control block timestamps and cadence, so a block count alone shrinks in wall-clock time
+/// whenever blocks are produced faster than nominal; the time span keeps the protection in seconds.
+const fn mixed_reorg_protection_policy(
+    depth: u64,
+    block_time_seconds: u64,
+) -> ReorgProtectionPolicy {
+    ReorgProtectionPolicy::blocks_and_elapsed(depth, depth * block_time_seconds)
+}
+
+/// Returns the reorg protection policy for the given chain.
+///
+/// Sequencer-validated chains and Ethereum require both their linking depth and the time span that depth
+/// covers at their nominal block time.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID to look up.
+///
+/// # Returns
+/// * `ReorgProtectionPolicy` - The linking block requirements for the chain.
+///
+/// # Panics
+/// Panics if the chain ID is invalid or unsupported.
+pub fn get_reorg_protection_policy(chain_id: u64) -> ReorgProtectionPolicy {
+    match chain_id {
+        OPTIMISM_CHAIN_ID => mixed_reorg_protection_policy(
+            REORG_PROTECTION_DEPTH_OPTIMISM,
+            OPSTACK_BLOCK_TIME_SECONDS,
+        ),
+        BASE_CHAIN_ID => {
+            mixed_reorg_protection_policy(REORG_PROTECTION_DEPTH_BASE, OPSTACK_BLOCK_TIME_SECONDS)
+        }
+        LINEA_CHAIN_ID => {
+            mixed_reorg_protection_policy(REORG_PROTECTION_DEPTH_LINEA, LINEA_BLOCK_TIME_SECONDS)
+        }
+        ETHEREUM_CHAIN_ID => mixed_reorg_protection_policy(
+            REORG_PROTECTION_DEPTH_ETHEREUM,
+            ETHEREUM_BLOCK_TIME_SECONDS,
+        ),
+        OPTIMISM_SEPOLIA_CHAIN_ID => mixed_reorg_protection_policy(
+            REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
+            OPSTACK_BLOCK_TIME_SECONDS,
+        ),
+        BASE_SEPOLIA_CHAIN_ID => mixed_reorg_protection_policy(
+            REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
+            OPSTACK_BLOCK_TIME_SECONDS,
+        ),
+        LINEA_SEPOLIA_CHAIN_ID => mixed_reorg_protection_policy(
+            REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
+            LINEA_BLOCK_TIME_SECONDS,
+        ),
+        ETHEREUM_SEPOLIA_CHAIN_ID => mixed_reorg_protection_policy(
+            REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
+            ETHEREUM_BLOCK_TIME_SECONDS,
+        ),
+        SCROLL_CHAIN_ID | SCROLL_SEPOLIA_CHAIN_ID => REORG_PROTECTION_POLICY_SCROLL,
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Errors returned when linking blocks fail the reorg protection checks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkingBlocksError {
+    /// Fewer linking blocks than the policy requires.
+    TooFewBlocks { length: u64, min: u64 },
+    /// More linking blocks than the policy allows.
+    TooManyBlocks { length: u64, max: u64 },
+    /// The linking blocks span less time than the policy requires.
+    InsufficientTimeSpan { elapsed: u64, min: u64 },
+    /// A linking block number is not its parent's number plus one.
+    NonSequentialNumber { index: usize, expected: u64, found: u64 },
+    /// A linking block timestamp is lower than its parent's timestamp.
+    NonMonotonicTimestamp { index: usize, parent: u64, found: u64 },
+    /// A linking block does not reference the hash of its parent.
+    NotHashLinked { index: usize },
+    /// The last linking block does not hash to the validated block hash.
+    HeadMismatch { expected: B256, found: B256 },
+    /// The ancestor of an ancestry claim is not the historical block.
+    AncestorMismatch { expected: B256, found: B256 },
+}
+
+impl core::fmt::Display for LinkingBlocksError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooFewBlocks { length, min } => write!(
+                f,
+                "chain length is less than reorg protection: {length} < {min}"
+            ),
+            Self::TooManyBlocks { length, max } => write!(
+                f,
+                "chain length exceeds maximum linking blocks: {length} > {max}"
+            ),
+            Self::InsufficientTimeSpan { elapsed, min } => write!(
+                f,
+                "chain time span is less than reorg protection: {elapsed}s < {min}s"
+            ),
+            Self::NonSequentialNumber {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "linking block {index} has number {found}, expected {expected}"
+            ),
+            Self::NonMonotonicTimestamp {
+                index,
+                parent,
+                found,
+            } => write!(
+                f,
+                "linking block {index} has timestamp {found} before parent timestamp {parent}"
+            ),
+            Self::NotHashLinked { index } => write!(f, "blocks not hashlinked at index {index}"),
+            Self::HeadMismatch { expected, found } => write!(
+                f,
+                "last hash doesnt correspond to verified hash: {found} != {expected}"
+            ),
+            Self::AncestorMismatch { expected, found } => write!(
+                f,
+                "ancestor hash doesnt correspond to historical hash: {found} != {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LinkingBlocksError {}
+
+/// Checks linking blocks against a reorg protection policy.
+///
+/// The checks are ordered from cheapest to most expensive: length bounds first, then block number
+/// and timestamp continuity, and only then the hash linking, which requires hashing every header.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID of the linking blocks, used to compute their hashes.
+/// * `policy` - The reorg protection policy to enforce.
+/// * `historical_hash` - The hash of the historical block.
+/// * `historical_header` - The header of the historical block.
+/// * `linking_blocks` - Blocks linking historical to current.
+/// * `current_hash` - The expected current block hash.
+///
+/// # Returns
+/// * `Result<(), LinkingBlocksError>` - `Ok` if the linking blocks satisfy the policy.
+pub fn check_linking_blocks(
+    chain_id: u64,
+    policy: &ReorgProtectionPolicy,
+    historical_hash: B256,
+    historical_header: &Header,
+    linking_blocks: &[RlpHeader<Header>],
+    current_hash: B256,
+) -> Result<(), LinkingBlocksError> {
+    let head_hash = link_headers(
+        chain_id,
+        policy,
+        historical_hash,
+        historical_header.number,
+        historical_header.timestamp,
+        linking_blocks,
+    )?;
+    // Ensure the final hash matches the expected current hash.
+    if head_hash != current_hash {
+        return Err(LinkingBlocksError::HeadMismatch {
+            expected: current_hash,
+            found: head_hash,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that headers extend the given parent block and returns the hash of the last header.
+///
+/// Performs every check of [`check_linking_blocks`] except the comparison against the current hash.
+fn link_headers(
+    chain_id: u64,
+    policy: &ReorgProtectionPolicy,
+    parent_hash: B256,
+    parent_number: u64,
+    parent_timestamp: u64,
+    headers: &[RlpHeader<Header>],
+) -> Result<B256, LinkingBlocksError> {
+    let chain_length = headers.len() as u64;
+    // Bound the chain length before doing any per-block work.
+    if chain_length > policy.max_blocks {
+        return Err(LinkingBlocksError::TooManyBlocks {
+            length: chain_length,
+            max: policy.max_blocks,
+        });
+    }
+    if let Some(min_blocks) = policy.min_blocks {
+        if chain_length < min_blocks {
+            return Err(LinkingBlocksError::TooFewBlocks {
+                length: chain_length,
+                min: min_blocks,
+            });
+        }
+    }
+
+    // Check that block numbers increase by one and timestamps never decrease.
+    let mut previous_number = parent_number;
+    let mut previous_timestamp = parent_timestamp;
+    for (index, header) in headers.iter().enumerate() {
+        let expected = previous_number + 1;
+        if header.number != expected {
+            return Err(LinkingBlocksError::NonSequentialNumber {
+                index,
+                expected,
+                found: header.number,
+            });
+        }
+        if header.timestamp < previous_timestamp {
+            return Err(LinkingBlocksError::NonMonotonicTimestamp {
+                index,
+                parent: previous_timestamp,
+                found: header.timestamp,
+            });
+        }
+        previous_number = header.number;
+        previous_timestamp = header.timestamp;
+    }
+
+    // Timestamps are monotonic at this point, so the span cannot underflow.
+    if let Some(min_elapsed_seconds) = policy.min_elapsed_seconds {
+        let elapsed = previous_timestamp - parent_timestamp;
+        if elapsed < min_elapsed_seconds {
+            return Err(LinkingBlocksError::InsufficientTimeSpan {
+                elapsed,
+                min: min_elapsed_seconds,
+            });
+        }
+    }
+
+    // Check that each block is hash-linked to its parent.
+    let mut previous_hash = parent_hash;
+    for (index, header) in headers.iter().enumerate() {
+        if header.parent_hash != previous_hash {
+            return Err(LinkingBlocksError::NotHashLinked { index });
+        }
+        previous_hash = get_block_hash(chain_id, header.inner());
+    }
+    Ok(previous_hash)
+}
+
+/// Validates block chain length and hash linking for reorg protection.
+///
+/// Ensures sufficient block confirmations and proper hash linking between blocks
+/// to prevent reorganization attacks. Checks that the chain satisfies the reorg protection policy of the chain
+/// (block count bounds and/or elapsed time), that block numbers and timestamps progress correctly,
+/// that each block is hash-linked to its parent, and that the final hash matches the expected current hash.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID to determine the reorg protection policy.
+/// * `historical_hash` - The hash of the historical block.
+/// * `historical_header` - The header of the historical block.
+/// * `linking_blocks` - Vector of blocks linking historical to current.
+/// * `current_hash` - The expected current block hash.
+///
+/// # Panics
+/// Panics if:
+/// * The linking blocks fail any check in [`check_linking_blocks`].
+/// * Chain ID is invalid or unsupported.
+pub fn validate_chain_length(
+    chain_id: u64,
+    historical_hash: B256,
+    historical_header: &Header,
+    linking_blocks: &[RlpHeader<Header>],
+    current_hash: B256,
+) {
+    // Determine the reorg protection policy for the given chain.
+    let policy = get_reorg_protection_policy(chain_id);
+    validate_chain_length_with_policy(
+        chain_id,
+        &policy,
+        historical_hash,
+        historical_header,
+        linking_blocks,
+        current_hash,
+    );
+}
+
+/// Validates chain length and block hash linking against the given reorg protection policy.
+///
+/// See [`validate_chain_length`]; used for chains whose policy is not built in.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID of the linking blocks.
+/// * `policy` - The reorg protection policy to enforce.
+/// * `historical_hash` - The hash of the historical block.
+/// * `historical_header` - The header of the historical block.
+/// * `linking_blocks` - Vector of block headers linking historical to current.
+/// * `current_hash` - The hash of the current block.
+///
+/// # Panics
+/// Panics if the linking blocks fail [`check_linking_blocks`].
+pub fn validate_chain_length_with_policy(
+    chain_id: u64,
+    policy: &ReorgProtectionPolicy,
+    historical_hash: B256,
+    historical_header: &Header,
+    linking_blocks: &[RlpHeader<Header>],
+    current_hash: B256,
+) {
+    if let Err(err) = check_linking_blocks(
+        chain_id,
+        policy,
+        historical_hash,
+        historical_header,
+        linking_blocks,
+        current_hash,
+    ) {
+        panic!("{err}");
+    }
+}
+
+/// Claim that one block is an ancestor of another block on the same chain.
+///
+/// Committed by the ancestry guest, which proves the claim incrementally: the first proof links an ancestor
+/// header to a head, and each later proof consumes the previous receipt and only the headers produced since
+/// its head. Consecutive batches can then reuse the linking work of earlier proofs instead of re-submitting
+/// overlapping linking blocks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AncestryClaim {
+    pub chain_id: u64,
+    pub ancestor_hash: B256,
+    pub ancestor_number: u64,
+    pub ancestor_timestamp: u64,
+    pub head_hash: B256,
+    pub head_number: u64,
+    pub head_timestamp: u64,
+}
+
+/// Encodes the journal of an ancestry proof.
+///
+/// The layout is `abi.encodePacked(bytes32 ancestryImageId, uint64 chainId, bytes32 ancestorHash,
+/// uint64 ancestorNumber, uint64 ancestorTimestamp, bytes32 headHash, uint64 headNumber, uint64 headTimestamp)`,
+/// where the image ID is encoded as its little-endian words, matching the RISC Zero digest bytes.
+///
+/// A guest cannot embed its own image ID, so the ancestry guest reads it from its input and commits it; the
+/// final verifier of a recursive chain must check it against the expected ancestry image ID.
+///
+/// # Arguments
+/// * `ancestry_image_id` - The image ID of the ancestry guest.
+/// * `claim` - The ancestry claim.
+///
+/// # Returns
+/// * `Bytes` - The journal.
+pub fn encode_ancestry_journal(ancestry_image_id: [u32; 8], claim: &AncestryClaim) -> Bytes {
+    let mut journal = Vec::with_capacity(144);
+    for word in ancestry_image_id {
+        journal.extend_from_slice(&word.to_le_bytes());
+    }
+    journal.extend_from_slice(&claim.chain_id.to_be_bytes());
+    journal.extend_from_slice(claim.ancestor_hash.as_slice());
+    journal.extend_from_slice(&claim.ancestor_number.to_be_bytes());
+    journal.extend_from_slice(&claim.ancestor_timestamp.to_be_bytes());
+    journal.extend_from_slice(claim.head_hash.as_slice());
+    journal.extend_from_slice(&claim.head_number.to_be_bytes());
+    journal.extend_from_slice(&claim.head_timestamp.to_be_bytes());
+    journal.into()
+}
+
+/// Policy for a single step of an ancestry proof: no minimum depth, bounded by the maximum linking blocks.
+const ANCESTRY_STEP_POLICY: ReorgProtectionPolicy = ReorgProtectionPolicy {
+    min_blocks: None,
+    min_elapsed_seconds: None,
+    max_blocks: MAX_LINKING_BLOCKS,
+};
+
+/// Starts an ancestry claim from an ancestor header and the headers linking it to a new head.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID of the headers.
+/// * `ancestor_header` - The header of the ancestor block.
+/// * `headers` - Headers linking the ancestor to the head.
+///
+/// # Returns
+/// * `AncestryClaim` - The claim that the ancestor is an ancestor of the last header.
+///
+/// # Panics
+/// Panics if the headers fail the structural checks in [`check_linking_blocks`].
+pub fn start_ancestry_claim(
+    chain_id: u64,
+    ancestor_header: &RlpHeader<Header>,
+    headers: &[RlpHeader<Header>],
+) -> AncestryClaim {
+    let ancestor_hash = get_block_hash(chain_id, ancestor_header.inner());
+    let claim = AncestryClaim {
+        chain_id,
+        ancestor_hash,
+        ancestor_number: ancestor_header.number,
+        ancestor_timestamp: ancestor_header.timestamp,
+        head_hash: ancestor_hash,
+        head_number: ancestor_header.number,
+        head_timestamp: ancestor_header.timestamp,
+    };
+    append_to_ancestry_claim(claim, headers)
+}
+
+/// Extends a previously proven ancestry claim with the headers produced since its head.
+///
+/// Adds an assumption that a receipt of the ancestry guest committing the previous claim exists; the
+/// assumption is resolved when the proof is composed with that receipt.
+///
+/// # Arguments
+/// * `ancestry_image_id` - The image ID of the ancestry guest, committed into the journal.
+/// * `previous` - The claim proven by the previous receipt.
+/// * `headers` - Headers linking the previous head to the new head.
+///
+/// # Returns
+/// * `AncestryClaim` - The claim that the previous ancestor is an ancestor of the last header.
+///
+/// # Panics
+/// Panics if:
+/// * The headers fail the structural checks in [`check_linking_blocks`].
+/// * The assumption can't be added.
+/// * Called outside the zkVM guest.
+pub fn extend_ancestry_claim(
+    ancestry_image_id: [u32; 8],
+    previous: &AncestryClaim,
+    headers: &[RlpHeader<Header>],
+) -> AncestryClaim {
+    let journal = encode_ancestry_journal(ancestry_image_id, previous);
+    add_assumption(ancestry_image_id, &journal);
+    append_to_ancestry_claim(previous.clone(), headers)
+}
+
+/// Moves the head of an ancestry claim to the last of the given headers.
+fn append_to_ancestry_claim(claim: AncestryClaim, headers: &[RlpHeader<Header>]) -> AncestryClaim {
+    let head_hash = match link_headers(
+        claim.chain_id,
+        &ANCESTRY_STEP_POLICY,
+        claim.head_hash,
+        claim.head_number,
+        claim.head_timestamp,
+        headers,
+    ) {
+        Ok(head_hash) => head_hash,
+        Err(err) => panic!("{err}"),
+    };
+    match headers.last() {
+        Some(head) => AncestryClaim {
+            head_hash,
+            head_number: head.number,
+            head_timestamp: head.timestamp,
+            ..claim
+        },
+        None => claim,
+    }
+}
+
+/// Checks an ancestry claim against a reorg protection policy.
+///
+/// The depth of the claim replaces the number of linking blocks. The maximum linking blocks bound only
+/// applies to each step of the ancestry proof, so it is not enforced here.
+///
+/// # Arguments
+/// * `policy` - The reorg protection policy to enforce.
+/// * `claim` - The ancestry claim.
+/// * `historical_hash` - The hash of the historical block.
+/// * `current_hash` - The expected current block hash.
+///
+/// # Returns
+/// * `Result<(), LinkingBlocksError>` - `Ok` if the claim satisfies the policy.
+pub fn check_ancestry_claim(
+    policy: &ReorgProtectionPolicy,
+    claim: &AncestryClaim,
+    historical_hash: B256,
+    current_hash: B256,
+) -> Result<(), LinkingBlocksError> {
+    if claim.ancestor_hash != historical_hash {
+        return Err(LinkingBlocksError::AncestorMismatch {
+            expected: historical_hash,
+            found: claim.ancestor_hash,
+        });
+    }
+    if claim.head_hash != current_hash {
+        return Err(LinkingBlocksError::HeadMismatch {
+            expected: current_hash,
+            found: claim.head_hash,
+        });
+    }
+    // Ancestry proofs only ever move the head forward, so neither difference can underflow.
+    if let Some(min_blocks) = policy.min_blocks {
+        let depth = claim.head_number - claim.ancestor_number;
+        if depth < min_blocks {
+            return Err(LinkingBlocksError::TooFewBlocks {
+                length: depth,
+                min: min_blocks,
+            });
+        }
+    }
+    if let Some(min_elapsed_seconds) = policy.min_elapsed_seconds {
+        let elapsed = claim.head_timestamp - claim.ancestor_timestamp;
+        if elapsed < min_elapsed_seconds {
+            return Err(LinkingBlocksError::InsufficientTimeSpan {
+                elapsed,
+                min: min_elapsed_seconds,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Validates reorg protection with a proven ancestry claim instead of linking blocks.
+///
+/// See [`validate_chain_length`]; the claim is verified through composition with an ancestry proof.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID of the claim.
+/// * `ancestry_image_id` - The image ID of the ancestry guest.
+/// * `policy` - The reorg protection policy to enforce.
+/// * `claim` - The ancestry claim proven by the ancestry guest.
+/// * `historical_hash` - The hash of the historical block.
+/// * `current_hash` - The expected current block hash.
+///
+/// # Panics
+/// Panics if:
+/// * The claim is for a different chain.
+/// * The claim fails [`check_ancestry_claim`].
+/// * The assumption can't be added.
+/// * Called outside the zkVM guest.
+pub fn validate_chain_length_with_ancestry(
+    chain_id: u64,
+    ancestry_image_id: [u32; 8],
+    policy: &ReorgProtectionPolicy,
+    claim: &AncestryClaim,
+    historical_hash: B256,
+    current_hash: B256,
+) {
+    assert_eq!(claim.chain_id, chain_id, "ancestry claim chain id mismatch");
+    if let Err(err) = check_ancestry_claim(policy, claim, historical_hash, current_hash) {
+        panic!("{err}");
+    }
+    let journal = encode_ancestry_journal(ancestry_image_id, claim);
+    add_assumption(ancestry_image_id, &journal);
+}
+
+/// Generalized index of `finalized_checkpoint.root` in a Deneb `BeaconState`.
+pub const FINALIZED_ROOT_GINDEX_DENEB: u64 = 105;
+/// Generalized index of `finalized_checkpoint.root` in an Electra `BeaconState`.
+pub const FINALIZED_ROOT_GINDEX_ELECTRA: u64 = 169;
+/// Generalized index of `execution_payload.block_hash` in a Deneb or Electra `BeaconBlockBody`.
+pub const EXECUTION_BLOCK_HASH_GINDEX: u64 = 812;
+/// First slot of the Electra fork on Ethereum mainnet.
+pub const ELECTRA_FORK_SLOT_ETHEREUM: u64 = 364_032 * 32;
+/// First slot of the Electra fork on Ethereum Sepolia.
+pub const ELECTRA_FORK_SLOT_ETHEREUM_SEPOLIA: u64 = 222_464 * 32;
+
+/// A beacon chain block header, as used for SSZ `hash_tree_root` computation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconBlockHeader {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: B256,
+    pub state_root: B256,
+    pub body_root: B256,
+}
+
+impl BeaconBlockHeader {
+    /// Computes the SSZ `hash_tree_root` of the header.
+    pub fn hash_tree_root(&self) -> B256 {
+        let mut leaves = [B256::ZERO; 8];
+        leaves[0] = ssz_uint64_leaf(self.slot);
+        leaves[1] = ssz_uint64_leaf(self.proposer_index);
+        leaves[2] = self.parent_root;
+        leaves[3] = self.state_root;
+        leaves[4] = self.body_root;
+        merkleize(&leaves)
+    }
+}
+
+/// Proof that an Ethereum execution block is finalized by the beacon chain.
+///
+/// The proof is anchored at an already validated execution block: its `parent_beacon_block_root`
+/// (EIP-4788) commits to the attested beacon block, whose state contains the finalized checkpoint.
+/// The finalized beacon block in turn commits to the execution block hash of the checkpoint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EthereumFinalityProof {
+    /// Header of the validated execution block the proof is anchored at.
+    pub anchor_header: RlpHeader<Header>,
+    /// Beacon block header referenced by the anchor's `parent_beacon_block_root`.
+    pub attested_beacon_header: BeaconBlockHeader,
+    /// Merkle branch of `finalized_checkpoint.root` in the attested beacon state.
+    pub finalized_root_branch: Vec<B256>,
+    /// Beacon block header of the finalized checkpoint.
+    pub finalized_beacon_header: BeaconBlockHeader,
+    /// Execution block hash of the finalized checkpoint.
+    pub finalized_block_hash: B256,
+    /// Merkle branch of `execution_payload.block_hash` in the finalized beacon block body.
+    pub execution_block_hash_branch: Vec<B256>,
+}
+
+/// Errors returned when an Ethereum finality proof fails validation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FinalityProofError {
+    /// The chain is not Ethereum mainnet or Sepolia.
+    UnsupportedChain { chain_id: u64 },
+    /// The anchor header doesn't hash to the validated block hash.
+    AnchorHashMismatch { expected: B256, found: B256 },
+    /// The anchor header predates EIP-4788 and has no parent beacon block root.
+    MissingParentBeaconBlockRoot,
+    /// The attested beacon header doesn't hash to the anchor's parent beacon block root.
+    AttestedHeaderMismatch { expected: B256, found: B256 },
+    /// The finalized checkpoint branch doesn't prove the finalized header at the fork's gindex.
+    InvalidFinalizedRootBranch { gindex: u64 },
+    /// The execution block hash branch doesn't prove the block hash in the finalized body.
+    InvalidExecutionBlockHashBranch,
+}
+
+impl core::fmt::Display for FinalityProofError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnsupportedChain { chain_id } => {
+                write!(f, "finality proof not supported for chain {chain_id}")
+            }
+            Self::AnchorHashMismatch { expected, found } => {
+                write!(f, "finality anchor hash mismatch: {found} != {expected}")
+            }
+            Self::MissingParentBeaconBlockRoot => {
+                write!(f, "finality anchor has no parent beacon block root")
+            }
+            Self::AttestedHeaderMismatch { expected, found } => {
+                write!(f, "attested beacon header mismatch: {found} != {expected}")
+            }
+            Self::InvalidFinalizedRootBranch { gindex } => {
+                write!(f, "invalid finalized checkpoint branch at gindex {gindex}")
+            }
+            Self::InvalidExecutionBlockHashBranch => {
+                write!(f, "invalid execution block hash branch")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FinalityProofError {}
+
+/// Checks an Ethereum finality proof and returns the finalized execution block hash.
+///
+/// The gindex of the finalized checkpoint depends on the fork of the attested beacon block:
+/// [`FINALIZED_ROOT_GINDEX_DENEB`] before the Electra fork slot, [`FINALIZED_ROOT_GINDEX_ELECTRA`] from it on.
+///
+/// # Arguments
+/// * `chain_id` - The Ethereum chain ID (mainnet or Sepolia).
+/// * `validated_hash` - The validated Ethereum block hash the proof is anchored at.
+/// * `proof` - The finality proof.
+///
+/// # Returns
+/// * `Result<B256, FinalityProofError>` - The execution block hash of the finalized checkpoint.
+pub fn check_finality_proof(
+    chain_id: u64,
+    validated_hash: B256,
+    proof: &EthereumFinalityProof,
+) -> Result<B256, FinalityProofError> {
+    let electra_fork_slot = match chain_id {
+        ETHEREUM_CHAIN_ID => ELECTRA_FORK_SLOT_ETHEREUM,
+        ETHEREUM_SEPOLIA_CHAIN_ID => ELECTRA_FORK_SLOT_ETHEREUM_SEPOLIA,
+        _ => return Err(FinalityProofError::UnsupportedChain { chain_id }),
+    };
+
+    // The anchor must be the validated block, which commits to the attested beacon block.
+    let anchor_hash = proof.anchor_header.hash_slow();
+    if anchor_hash != validated_hash {
+        return Err(FinalityProofError::AnchorHashMismatch {
+            expected: validated_hash,
+            found: anchor_hash,
+        });
+    }
+    let attested_root = proof
+        .anchor_header
+        .parent_beacon_block_root
+        .ok_or(FinalityProofError::MissingParentBeaconBlockRoot)?;
+    let attested_header_root = proof.attested_beacon_header.hash_tree_root();
+    if attested_header_root != attested_root {
+        return Err(FinalityProofError::AttestedHeaderMismatch {
+            expected: attested_root,
+            found: attested_header_root,
+        });
+    }
+
+    // Prove the finalized checkpoint root in the attested beacon state.
+    let finalized_root_gindex = if proof.attested_beacon_header.slot >= electra_fork_slot {
+        FINALIZED_ROOT_GINDEX_ELECTRA
+    } else {
+        FINALIZED_ROOT_GINDEX_DENEB
+    };
+    if !is_valid_merkle_branch(
+        proof.finalized_beacon_header.hash_tree_root(),
+        &proof.finalized_root_branch,
+        finalized_root_gindex,
+        proof.attested_beacon_header.state_root,
+    ) {
+        return Err(FinalityProofError::InvalidFinalizedRootBranch {
+            gindex: finalized_root_gindex,
+        });
+    }
+
+    // Prove the execution block hash in the finalized beacon block body.
+    if !is_valid_merkle_branch(
+        proof.finalized_block_hash,
+        &proof.execution_block_hash_branch,
+        EXECUTION_BLOCK_HASH_GINDEX,
+        proof.finalized_beacon_header.body_root,
+    ) {
+        return Err(FinalityProofError::InvalidExecutionBlockHashBranch);
+    }
+    Ok(proof.finalized_block_hash)
+}
+
+/// Verifies an Ethereum finality proof and returns the finalized execution block hash.
+///
+/// # Arguments
+/// * `chain_id` - The Ethereum chain ID (mainnet or Sepolia).
+/// * `validated_hash` - The validated Ethereum block hash the proof is anchored at.
+/// * `proof` - The finality proof.
+///
+/// # Returns
+/// * `B256` - The execution block hash of the finalized checkpoint.
+///
+/// # Panics
+/// Panics if the proof fails any check of [`check_finality_proof`].
+pub fn get_finalized_ethereum_block_hash(
+    chain_id: u64,
+    validated_hash: B256,
+    proof: &EthereumFinalityProof,
+) -> B256 {
+    match check_finality_proof(chain_id, validated_hash, proof) {
+        Ok(finalized_block_hash) => finalized_block_hash,
+        Err(err) => panic!("{err}"),
+    }
+}
+
+/// Validates that a block is an ancestor of a finalized Ethereum checkpoint.
+///
+/// Alternative to [`validate_chain_length`] for Ethereum: instead of requiring a fixed number of linking
+/// blocks on top of the historical block, the linking blocks must lead from the historical block to the
+/// execution block of a finalized checkpoint, so the proof relies on Casper finality.
+///
+/// # Arguments
+/// * `chain_id` - The Ethereum chain ID (mainnet or Sepolia).
+/// * `historical_hash` - The hash of the historical block.
+/// * `historical_header` - The header of the historical block.
+/// * `linking_blocks` - Blocks linking the historical block to the finalized block.
+/// * `validated_hash` - The validated Ethereum block hash the finality proof is anchored at.
+/// * `proof` - The finality proof.
+///
+/// # Panics
+/// Panics if:
+/// * The finality proof is invalid.
+/// * The linking blocks fail the structural checks in [`check_linking_blocks`].
+/// * The last linking block is not the finalized block.
+pub fn validate_chain_to_finalized_checkpoint(
+    chain_id: u64,
+    historical_hash: B256,
+    historical_header: &Header,
+    linking_blocks: &[RlpHeader<Header>],
+    validated_hash: B256,
+    proof: &EthereumFinalityProof,
+) {
+    let finalized_block_hash = get_finalized_ethereum_block_hash(chain_id, validated_hash, proof);
+
+    // Finality replaces the depth requirement, but the maximum depth still applies.
+    let policy = ReorgProtectionPolicy {
+        min_blocks: None,
+        min_elapsed_seconds: None,
+        max_blocks: get_reorg_protection_policy(chain_id).max_blocks,
+    };
+    if let Err(err) = check_linking_blocks(
+        chain_id,
+        &policy,
+        historical_hash,
+        historical_header,
+        linking_blocks,
+        finalized_block_hash,
+    ) {
+        panic!("{err}");
+    }
+}
+
+/// Encodes a `uint64` as an SSZ leaf (little-endian, zero padded).
+fn ssz_uint64_leaf(value: u64) -> B256 {
+    let mut leaf = B256::ZERO;
+    leaf[..8].copy_from_slice(&value.to_le_bytes());
+    leaf
+}
+
+/// Hashes two SSZ nodes into their parent.
+fn sha256_pair(left: &B256, right: &B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// Merkleizes a power-of-two number of SSZ leaves.
+fn merkleize(leaves: &[B256]) -> B256 {
+    debug_assert!(leaves.len().is_power_of_two());
+    let mut layer = leaves.to_vec();
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| sha256_pair(&pair[0], &pair[1]))
+            .collect();
+    }
+    layer[0]
+}
+
+/// Verifies an SSZ Merkle branch for `leaf` at generalized index `gindex` against `root`.
+fn is_valid_merkle_branch(leaf: B256, branch: &[B256], gindex: u64, root: B256) -> bool {
+    let depth = 63 - gindex.leading_zeros();
+    if branch.len() != depth as usize {
+        return false;
+    }
+    let mut node = leaf;
+    let mut index = gindex;
+    for sibling in branch {
+        node = if index & 1 == 1 {
+            sha256_pair(sibling, &node)
+        } else {
+            sha256_pair(&node, sibling)
+        };
+        index >>= 1;
+    }
+    node == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    const ALL_GAME_TYPES: [u32; 7] = [
+        GAME_TYPE_CANNON,
+        GAME_TYPE_PERMISSIONED_CANNON,
+        GAME_TYPE_ASTERISC,
+        GAME_TYPE_ASTERISC_KONA,
+        GAME_TYPE_SUPER_CANNON,
+        GAME_TYPE_SUPER_PERMISSIONED_CANNON,
+        GAME_TYPE_OP_SUCCINCT,
+    ];
+
+    fn assert_game_types(chain_id: u64, accepted: &[u32]) {
+        let policy = get_dispute_game_policy(chain_id);
+        for game_type in ALL_GAME_TYPES {
+            let result = check_game_type(&policy, game_type, U256::from(game_type));
+            if accepted.contains(&game_type) {
+                assert_eq!(result, Ok(()), "chain {chain_id} game type {game_type}");
+            } else {
+                assert_eq!(
+                    result,
+                    Err(DisputeGameError::RespectedGameTypeNotAccepted {
+                        respected: game_type
+                    }),
+                    "chain {chain_id} game type {game_type}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mainnet_dispute_game_types() {
+        let accepted = [
+            GAME_TYPE_CANNON,
+            GAME_TYPE_PERMISSIONED_CANNON,
+            GAME_TYPE_SUPER_CANNON,
+            GAME_TYPE_SUPER_PERMISSIONED_CANNON,
+        ];
+        assert_game_types(OPTIMISM_CHAIN_ID, &accepted);
+        assert_game_types(BASE_CHAIN_ID, &accepted);
+    }
+
+    #[test]
+    fn sepolia_dispute_game_types() {
+        assert_game_types(OPTIMISM_SEPOLIA_CHAIN_ID, &ALL_GAME_TYPES);
+        assert_game_types(BASE_SEPOLIA_CHAIN_ID, &ALL_GAME_TYPES);
+    }
+
+    #[test]
+    fn game_not_of_respected_type() {
+        let policy = get_dispute_game_policy(OPTIMISM_SEPOLIA_CHAIN_ID);
+        for respected in ALL_GAME_TYPES {
+            for game_type in ALL_GAME_TYPES.into_iter().filter(|&t| t != respected) {
+                assert_eq!(
+                    check_game_type(&policy, respected, U256::from(game_type)),
+                    Err(DisputeGameError::GameTypeNotRespected {
+                        game_type: U256::from(game_type),
+                        respected,
+                    })
+                );
+            }
+        }
+    }
+
+    const MATURITY_POLICY: DisputeGamePolicy = DisputeGamePolicy {
+        accepted_game_types: &[GAME_TYPE_CANNON],
+        proof_maturity_margin_seconds: 100,
+        strict_proof_maturity: false,
+        require_respected_when_created: true,
+    };
+
+    #[test]
+    fn proof_not_yet_mature() {
+        // 1000s delay minus the 100s margin requires more than 900s to have passed.
+        assert_eq!(
+            check_proof_maturity(&MATURITY_POLICY, 1_500, 1_000, U256::from(1_000)),
+            Err(DisputeGameError::InsufficientMaturity {
+                elapsed: 500,
+                required: 900
+            })
+        );
+        let strict = DisputeGamePolicy {
+            strict_proof_maturity: true,
+            ..MATURITY_POLICY
+        };
+        assert_eq!(
+            check_proof_maturity(&strict, 1_950, 1_000, U256::from(1_000)),
+            Err(DisputeGameError::InsufficientMaturity {
+                elapsed: 950,
+                required: 1_000
+            })
+        );
+    }
+
+    #[test]
+    fn proof_maturity_boundary() {
+        // Like the portal, exactly the required time is not enough.
+        assert_eq!(
+            check_proof_maturity(&MATURITY_POLICY, 1_900, 1_000, U256::from(1_000)),
+            Err(DisputeGameError::InsufficientMaturity {
+                elapsed: 900,
+                required: 900
+            })
+        );
+        assert_eq!(
+            check_proof_maturity(&MATURITY_POLICY, 1_901, 1_000, U256::from(1_000)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn proof_maturity_out_of_range() {
+        // A resolution time after the Ethereum block must not underflow into a large elapsed time.
+        assert_eq!(
+            check_proof_maturity(&MATURITY_POLICY, 1_000, u64::MAX, U256::from(1_000)),
+            Err(DisputeGameError::ResolvedInFuture {
+                resolved_at: u64::MAX,
+                current: 1_000
+            })
+        );
+        let delay = U256::from(u64::MAX) + U256::from(1);
+        assert_eq!(
+            check_proof_maturity(&MATURITY_POLICY, 10_000, 1_000, delay),
+            Err(DisputeGameError::MaturityDelayOutOfRange { delay })
+        );
+        assert_eq!(
+            check_proof_maturity(&MATURITY_POLICY, 10_000, 1_000, U256::from(50)),
+            Err(DisputeGameError::MarginExceedsDelay {
+                delay: 50,
+                margin: 100
+            })
+        );
+    }
+
+    #[test]
+    fn proof_mature() {
+        assert_eq!(
+            check_proof_maturity(&MATURITY_POLICY, 1_000_000, 1_000, U256::from(1_000)),
+            Ok(())
+        );
+        let strict = DisputeGamePolicy {
+            strict_proof_maturity: true,
+            ..MATURITY_POLICY
+        };
+        assert_eq!(
+            check_proof_maturity(&strict, 2_001, 1_000, U256::from(1_000)),
+            Ok(())
+        );
+        // The strict policy ignores the margin, even if it exceeds the delay.
+        assert_eq!(
+            check_proof_maturity(&strict, 1_051, 1_000, U256::from(50)),
+            Ok(())
+        );
+    }
+
+    fn schedule_entry(from: u64, until: Option<u64>) -> SequencerScheduleEntry {
+        SequencerScheduleEntry {
+            sequencer: Address::repeat_byte(from as u8),
+            valid_from_block: from,
+            valid_until_block: until,
+        }
+    }
+
+    #[test]
+    fn builtin_sequencer_schedules() {
+        for chain_id in [
+            OPTIMISM_CHAIN_ID,
+            BASE_CHAIN_ID,
+            LINEA_CHAIN_ID,
+            OPTIMISM_SEPOLIA_CHAIN_ID,
+            BASE_SEPOLIA_CHAIN_ID,
+            LINEA_SEPOLIA_CHAIN_ID,
+        ] {
+            assert_eq!(
+                check_sequencer_schedule(get_sequencer_schedule(chain_id)),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn sequencer_schedule_rotation() {
+        let schedule = [schedule_entry(0, Some(99)), schedule_entry(100, None)];
+        assert_eq!(check_sequencer_schedule(&schedule), Ok(()));
+        assert!(schedule[0].covers(99) && !schedule[0].covers(100));
+        assert!(schedule[1].covers(100) && schedule[1].covers(u64::MAX));
+    }
+
+    #[test]
+    fn sequencer_schedule_overlaps() {
+        assert_eq!(
+            check_sequencer_schedule(&[schedule_entry(0, Some(100)), schedule_entry(100, None)]),
+            Err(SequencerScheduleError::Overlap { index: 1 })
+        );
+        assert_eq!(
+            check_sequencer_schedule(&[schedule_entry(0, None), schedule_entry(100, None)]),
+            Err(SequencerScheduleError::Overlap { index: 1 })
+        );
+        assert_eq!(
+            check_sequencer_schedule(&[
+                schedule_entry(100, Some(199)),
+                schedule_entry(0, Some(99))
+            ]),
+            Err(SequencerScheduleError::Overlap { index: 1 })
+        );
+        assert_eq!(
+            check_sequencer_schedule(&[schedule_entry(100, Some(99))]),
+            Err(SequencerScheduleError::EmptyRange { index: 0 })
+        );
+    }
+
+    const TEST_HARDFORKS: OpStackHardforks = OpStackHardforks {
+        canyon_time: 100,
+        ecotone_time: 200,
+        isthmus_time: 300,
+    };
+
+    fn opstack_payload(
+        version: OpStackPayloadVersion,
+        block_number: u64,
+        timestamp: u64,
+    ) -> Vec<u8> {
+        let mut payload = vec![0u8; version.fixed_len()];
+        payload[PAYLOAD_BLOCK_NUMBER_OFFSET..PAYLOAD_BLOCK_NUMBER_OFFSET + 8]
+            .copy_from_slice(&block_number.to_le_bytes());
+        payload[PAYLOAD_TIMESTAMP_OFFSET..PAYLOAD_TIMESTAMP_OFFSET + 8]
+            .copy_from_slice(&timestamp.to_le_bytes());
+        payload[PAYLOAD_EXTRA_DATA_OFFSET..PAYLOAD_EXTRA_DATA_OFFSET + 4]
+            .copy_from_slice(&(version.fixed_len() as u32).to_le_bytes());
+        payload[PAYLOAD_BLOCK_HASH_OFFSET..PAYLOAD_BLOCK_HASH_OFFSET + 32].fill(0xbb);
+        if version == OpStackPayloadVersion::V4 {
+            payload[PAYLOAD_WITHDRAWALS_ROOT_OFFSET..PAYLOAD_WITHDRAWALS_ROOT_OFFSET + 32]
+                .fill(0xcc);
+        }
+        let mut data = vec![0xaa; version.envelope_prefix_len()];
+        data.extend_from_slice(&payload);
+        data
+    }
+
+    #[test]
+    fn decode_opstack_payload_versions() {
+        let cases = [
+            (OpStackPayloadVersion::V1, 99),
+            (OpStackPayloadVersion::V2, 100),
+            (OpStackPayloadVersion::V3, 200),
+            (OpStackPayloadVersion::V4, 300),
+        ];
+        for (version, timestamp) in cases {
+            let data = opstack_payload(version, 1_234, timestamp);
+            let header = decode_opstack_payload_with_hardforks(&TEST_HARDFORKS, &data).unwrap();
+            let enveloped = matches!(
+                version,
+                OpStackPayloadVersion::V3 | OpStackPayloadVersion::V4
+            );
+            assert_eq!(
+                header,
+                OpStackPayloadHeader {
+                    version,
+                    parent_beacon_block_root: enveloped.then_some(B256::repeat_byte(0xaa)),
+                    block_number: 1_234,
+                    timestamp,
+                    block_hash: B256::repeat_byte(0xbb),
+                    withdrawals_root: (version == OpStackPayloadVersion::V4)
+                        .then_some(B256::repeat_byte(0xcc)),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn decode_opstack_payload_fork_mismatch() {
+        let data = opstack_payload(OpStackPayloadVersion::V2, 1_234, 250);
+        assert_eq!(
+            decode_opstack_payload_with_hardforks(&TEST_HARDFORKS, &data),
+            Err(OpStackPayloadError::ForkMismatch {
+                version: OpStackPayloadVersion::V2,
+                expected: OpStackPayloadVersion::V3,
+            })
+        );
+        let data = opstack_payload(OpStackPayloadVersion::V4, 1_234, 299);
+        assert_eq!(
+            decode_opstack_payload_with_hardforks(&TEST_HARDFORKS, &data),
+            Err(OpStackPayloadError::ForkMismatch {
+                version: OpStackPayloadVersion::V4,
+                expected: OpStackPayloadVersion::V3,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_opstack_payload_unknown_layout() {
+        assert_eq!(
+            decode_opstack_payload_with_hardforks(&TEST_HARDFORKS, &[0u8; 10]),
+            Err(OpStackPayloadError::UnknownLayout { length: 10 })
+        );
+        let mut data = opstack_payload(OpStackPayloadVersion::V2, 1_234, 150);
+        data[PAYLOAD_EXTRA_DATA_OFFSET] ^= 1;
+        assert_eq!(
+            decode_opstack_payload_with_hardforks(&TEST_HARDFORKS, &data),
+            Err(OpStackPayloadError::UnknownLayout { length: data.len() })
+        );
+    }
+
+    #[test]
+    fn opstack_payload_version_at_builtin_forks() {
+        for chain_id in [OPTIMISM_CHAIN_ID, OPTIMISM_SEPOLIA_CHAIN_ID] {
+            let hardforks = get_opstack_hardforks(chain_id);
+            let boundaries = [
+                (
+                    hardforks.canyon_time,
+                    OpStackPayloadVersion::V1,
+                    OpStackPayloadVersion::V2,
+                ),
+                (
+                    hardforks.ecotone_time,
+                    OpStackPayloadVersion::V2,
+                    OpStackPayloadVersion::V3,
+                ),
+                (
+                    hardforks.isthmus_time,
+                    OpStackPayloadVersion::V3,
+                    OpStackPayloadVersion::V4,
+                ),
+            ];
+            for (fork_time, before, after) in boundaries {
+                assert_eq!(
+                    OpStackPayloadVersion::at_timestamp(&hardforks, fork_time - 1),
+                    before
+                );
+                assert_eq!(
+                    OpStackPayloadVersion::at_timestamp(&hardforks, fork_time),
+                    after
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn linea_extra_data_boundary_lengths() {
+        let expected = LINEA_EXTRA_DATA_VANITY_LEN + LINEA_EXTRA_DATA_SEAL_LEN;
+        for length in [0, 64, 65, 96, 98] {
+            assert_eq!(
+                parse_linea_extra_data(&Bytes::from(vec![0u8; length])),
+                Err(LineaExtraDataError::InvalidLength { length, expected })
+            );
+        }
+        let extra_data = Bytes::from(vec![0u8; 97]);
+        let parsed = parse_linea_extra_data(&extra_data).unwrap();
+        assert_eq!(parsed.vanity.len(), LINEA_EXTRA_DATA_VANITY_LEN);
+        assert_eq!(parsed.signature.len(), LINEA_EXTRA_DATA_SEAL_LEN);
+    }
+
+    #[test]
+    fn linea_extra_data_versions() {
+        for version in 0..=u8::MAX {
+            let mut extra_data = vec![0u8; 97];
+            extra_data[0] = version;
+            let result = parse_linea_extra_data(&Bytes::from(extra_data));
+            if LINEA_EXTRA_DATA_VERSIONS.contains(&version) {
+                assert_eq!(result.unwrap().version(), version);
+            } else {
+                assert_eq!(
+                    result,
+                    Err(LineaExtraDataError::UnsupportedVersion { version })
+                );
+            }
+        }
+    }
+
+    fn qbft_extra_data(validators: &[Address], round: u32, seals: &[Bytes]) -> Bytes {
+        use alloy_rlp::Encodable;
+
+        let mut payload = Vec::new();
+        Bytes::from(vec![0u8; 32]).encode(&mut payload);
+        validators.to_vec().encode(&mut payload);
+        // No vote.
+        payload.push(alloy_rlp::EMPTY_LIST_CODE);
+        round.encode(&mut payload);
+        seals.to_vec().encode(&mut payload);
+        let mut out = Vec::new();
+        alloy_rlp::Header {
+            list: true,
+            payload_length: payload.len(),
+        }
+        .encode(&mut out);
+        out.extend_from_slice(&payload);
+        out.into()
+    }
+
+    fn qbft_validators() -> Vec<Address> {
+        (1..=4).map(Address::repeat_byte).collect()
+    }
+
+    #[test]
+    fn decode_qbft_extra_data_round_trip() {
+        let validators = qbft_validators();
+        let seals = vec![Bytes::from(vec![0x11; 65]), Bytes::from(vec![0x22; 65])];
+        let decoded = decode_qbft_extra_data(&qbft_extra_data(&validators, 3, &seals)).unwrap();
+        assert_eq!(decoded.vanity, Bytes::from(vec![0u8; 32]));
+        assert_eq!(decoded.validators, validators);
+        assert_eq!(decoded.round, 3);
+        assert_eq!(decoded.committed_seals, seals);
+    }
+
+    #[test]
+    fn encode_qbft_extra_data_without_seals() {
+        let validators = qbft_validators();
+        let seals = vec![Bytes::from(vec![0x11; 65])];
+        let decoded = decode_qbft_extra_data(&qbft_extra_data(&validators, 3, &seals)).unwrap();
+        assert_eq!(
+            decoded.encode_without_seals(3),
+            qbft_extra_data(&validators, 3, &[])
+        );
+        assert_eq!(
+            decoded.encode_without_seals(0),
+            qbft_extra_data(&validators, 0, &[])
+        );
+    }
+
+    #[test]
+    fn decode_qbft_extra_data_invalid() {
+        let extra_data = qbft_extra_data(&qbft_validators(), 0, &[]);
+        // Not a list.
+        assert!(matches!(
+            decode_qbft_extra_data(&[0x80]),
+            Err(QbftError::Rlp(_))
+        ));
+        // Truncated.
+        assert!(matches!(
+            decode_qbft_extra_data(&extra_data[..extra_data.len() - 1]),
+            Err(QbftError::Rlp(_))
+        ));
+        // Trailing bytes.
+        let mut trailing = extra_data.to_vec();
+        trailing.push(0x80);
+        assert!(matches!(
+            decode_qbft_extra_data(&trailing),
+            Err(QbftError::Rlp(_))
+        ));
+    }
+
+    #[test]
+    fn qbft_quorum_sizes() {
+        let quorums = [
+            (1, 1),
+            (2, 2),
+            (3, 2),
+            (4, 3),
+            (5, 4),
+            (6, 4),
+            (7, 5),
+            (10, 7),
+        ];
+        for (validator_count, quorum) in quorums {
+            assert_eq!(
+                qbft_quorum(validator_count),
+                quorum,
+                "{validator_count} validators"
+            );
+        }
+    }
+
+    #[test]
+    fn qbft_seals_invalid() {
+        let validators = qbft_validators();
+        let header = Header {
+            extra_data: qbft_extra_data(&validators, 0, &[Bytes::from(vec![0x11; 64])]),
+            ..Default::default()
+        };
+        assert_eq!(
+            check_qbft_seals(&header, &validators[..3]),
+            Err(QbftError::ValidatorSetMismatch)
+        );
+        assert_eq!(
+            check_qbft_seals(&header, &validators),
+            Err(QbftError::InvalidSealLength {
+                index: 0,
+                length: 64
+            })
+        );
+        let header = Header {
+            extra_data: qbft_extra_data(&validators, 0, &[]),
+            ..Default::default()
+        };
+        assert_eq!(
+            check_qbft_seals(&header, &validators),
+            Err(QbftError::InsufficientQuorum {
+                signers: 0,
+                quorum: 3
+            })
+        );
+    }
+
+    #[test]
+    fn configured_qbft_validator_set() {
+        const VALIDATORS: &[Address] = &[Address::repeat_byte(1), Address::repeat_byte(2)];
+        assert_eq!(
+            get_qbft_validator_set(&QbftValidatorSet::Configured(VALIDATORS), None),
+            Ok(VALIDATORS.to_vec())
+        );
+    }
+
+    #[test]
+    fn l1_qbft_validator_set_without_ethereum_environment() {
+        let registry = Address::repeat_byte(0xaa);
+        assert_eq!(
+            get_qbft_validator_set(&QbftValidatorSet::L1 { registry }, None),
+            Err(QbftError::ValidatorSetUnavailable { registry })
+        );
+    }
+
+    #[test]
+    fn linea_consensus_schedule_seals() {
+        const VALIDATORS: &[Address] = &[
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            Address::repeat_byte(3),
+            Address::repeat_byte(4),
+        ];
+        let registry = Address::repeat_byte(0xaa);
+        let schedule = [
+            LineaConsensusScheduleEntry {
+                consensus: LineaConsensus::Sequencer,
+                valid_from_block: 10,
+            },
+            LineaConsensusScheduleEntry {
+                consensus: LineaConsensus::Qbft {
+                    validators: QbftValidatorSet::Configured(VALIDATORS),
+                },
+                valid_from_block: 100,
+            },
+            LineaConsensusScheduleEntry {
+                consensus: LineaConsensus::Qbft {
+                    validators: QbftValidatorSet::L1 { registry },
+                },
+                valid_from_block: 200,
+            },
+        ];
+        assert_eq!(find_linea_consensus(&schedule, 9), None);
+
+        // Sequencer blocks require a sequencer seal in the extra data.
+        let header = Header {
+            number: 99,
+            ..Default::default()
+        };
+        let consensus = find_linea_consensus(&schedule, header.number).unwrap();
+        assert_eq!(consensus, LineaConsensus::Sequencer);
+        assert!(matches!(
+            check_linea_seals(&consensus, LINEA_CHAIN_ID, &header, None),
+            Err(LineaSealError::ExtraData(_))
+        ));
+
+        // QBFT blocks with a configured validator set require a quorum of committed seals.
+        let header = Header {
+            number: 199,
+            extra_data: qbft_extra_data(&qbft_validators(), 0, &[]),
+            ..Default::default()
+        };
+        let consensus = find_linea_consensus(&schedule, header.number).unwrap();
+        assert_eq!(
+            check_linea_seals(&consensus, LINEA_CHAIN_ID, &header, None),
+            Err(LineaSealError::Qbft(QbftError::InsufficientQuorum {
+                signers: 0,
+                quorum: 3
+            }))
+        );
+
+        // QBFT blocks with a validator set on L1 can't be checked without L1 inclusion.
+        let header = Header {
+            number: 200,
+            ..header
+        };
+        let consensus = find_linea_consensus(&schedule, header.number).unwrap();
+        assert_eq!(
+            check_linea_seals(&consensus, LINEA_CHAIN_ID, &header, None),
+            Err(LineaSealError::Qbft(QbftError::ValidatorSetUnavailable {
+                registry
+            }))
+        );
+    }
+
+    #[test]
+    fn opstack_output_root_known_answer() {
+        // Reference vector of the v0 output root encoding used by kona and op-node.
+        let state_root = B256::left_padding_from(&[0xbe, 0xef]);
+        let message_passer_storage_root = B256::left_padding_from(&[0xba, 0xbe]);
+        let block_hash = B256::left_padding_from(&[0xc0, 0xde]);
+        assert_eq!(
+            opstack_output_root(state_root, message_passer_storage_root, block_hash),
+            b256!("0c39fb6b07cf6694b13e63e59f7b15255be1c93a4d6d3e0da6c99729647c0d11")
+        );
+
+        let header = Header {
+            number: 100,
+            state_root,
+            ..Default::default()
+        };
+        assert_eq!(
+            compute_opstack_output_root(&header, message_passer_storage_root),
+            opstack_output_root(state_root, message_passer_storage_root, header.hash_slow())
+        );
+    }
+
+    #[test]
+    fn super_root_known_answer() {
+        // Reference vector of the v1 super root encoding used by kona and op-supervisor.
+        let proof = SuperRootProof {
+            version: SUPER_ROOT_VERSION_V1,
+            timestamp: 10,
+            output_roots: vec![
+                SuperRootOutput {
+                    chain_id: 1,
+                    output_root: B256::ZERO,
+                },
+                SuperRootOutput {
+                    chain_id: 2,
+                    output_root: B256::ZERO,
+                },
+            ],
+        };
+        assert_eq!(
+            proof.super_root(),
+            b256!("0980033cbf4337f614a2401ab7efbfdc66ab647812f1c98d891d92ddfb376541")
+        );
+    }
+
+    #[test]
+    fn super_root_proof_checks() {
+        let output_root = B256::repeat_byte(0xaa);
+        let proof = SuperRootProof {
+            version: SUPER_ROOT_VERSION_V1,
+            timestamp: 1_000,
+            output_roots: vec![
+                SuperRootOutput {
+                    chain_id: OPTIMISM_CHAIN_ID,
+                    output_root,
+                },
+                SuperRootOutput {
+                    chain_id: BASE_CHAIN_ID,
+                    output_root: B256::repeat_byte(0xbb),
+                },
+            ],
+        };
+        let header = Header {
+            timestamp: 1_000,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            check_super_root_proof(OPTIMISM_CHAIN_ID, &header, output_root, &proof),
+            Ok(proof.super_root())
+        );
+        assert_eq!(proof.output_root(LINEA_CHAIN_ID), None);
+        assert_eq!(
+            check_super_root_proof(LINEA_CHAIN_ID, &header, output_root, &proof),
+            Err(SuperRootError::ChainNotIncluded {
+                chain_id: LINEA_CHAIN_ID
+            })
+        );
+        assert_eq!(
+            check_super_root_proof(BASE_CHAIN_ID, &header, output_root, &proof),
+            Err(SuperRootError::OutputRootMismatch {
+                expected: output_root,
+                found: B256::repeat_byte(0xbb)
+            })
+        );
+
+        let newer = Header {
+            timestamp: 1_001,
+            ..header.clone()
+        };
+        assert_eq!(
+            check_super_root_proof(OPTIMISM_CHAIN_ID, &newer, output_root, &proof),
+            Err(SuperRootError::BlockAfterTimestamp {
+                block_timestamp: 1_001,
+                timestamp: 1_000
+            })
+        );
+
+        let v0 = SuperRootProof {
+            version: 0,
+            ..proof.clone()
+        };
+        assert_eq!(
+            check_super_root_proof(OPTIMISM_CHAIN_ID, &header, output_root, &v0),
+            Err(SuperRootError::UnsupportedVersion { version: 0 })
+        );
+    }
+
+    #[test]
+    fn super_root_game_types() {
+        for game_type in ALL_GAME_TYPES {
+            assert_eq!(
+                is_super_root_game_type(game_type),
+                game_type == GAME_TYPE_SUPER_CANNON
+                    || game_type == GAME_TYPE_SUPER_PERMISSIONED_CANNON
+            );
+        }
+    }
+
+    fn rollup_config(l1_chain_id: u64, l2_chain_id: u64) -> OpStackRollupConfig {
+        OpStackRollupConfig {
+            l1_chain_id,
+            l2_chain_id,
+            deposit_contract_address: Address::repeat_byte(1),
+            l1_system_config_address: Address::repeat_byte(2),
+            canyon_time: Some(0),
+            ecotone_time: Some(0),
+            isthmus_time: None,
+            unsafe_block_signer: Address::repeat_byte(3),
+            reorg_protection_depth: 10,
+        }
+    }
+
+    #[test]
+    fn target_chain_ids() {
+        assert_eq!(
+            check_target_chain_ids(
+                BASE_CHAIN_ID,
+                None,
+                &[],
+                2,
+                2,
+                &[LINEA_CHAIN_ID, LINEA_CHAIN_ID]
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_target_chain_ids(BASE_CHAIN_ID, None, &[], 1, 2, &[LINEA_CHAIN_ID]),
+            Err(TargetChainError::LengthMismatch {
+                accounts: 1,
+                assets: 2,
+                target_chain_ids: 1
+            })
+        );
+        assert_eq!(
+            check_target_chain_ids(LINEA_CHAIN_ID, None, &[], 1, 1, &[LINEA_CHAIN_ID]),
+            Err(TargetChainError::TargetIsSource {
+                index: 0,
+                chain_id: LINEA_CHAIN_ID
+            })
+        );
+        assert_eq!(
+            check_target_chain_ids(LINEA_CHAIN_ID, None, &[], 1, 1, &[BASE_SEPOLIA_CHAIN_ID]),
+            Err(TargetChainError::NetworkMismatch {
+                index: 0,
+                chain_id: BASE_SEPOLIA_CHAIN_ID
+            })
+        );
+        assert_eq!(
+            check_target_chain_ids(BASE_CHAIN_ID, None, &[], 1, 1, &[OPTIMISM_CHAIN_ID]),
+            Err(TargetChainError::TargetNotHost {
+                index: 0,
+                chain_id: OPTIMISM_CHAIN_ID,
+                host: LINEA_CHAIN_ID
+            })
+        );
+    }
+
+    #[test]
+    fn configured_target_chain_ids() {
+        let configured = rollup_config(ETHEREUM_CHAIN_ID, 1_000_001);
+        let configured_sepolia = rollup_config(ETHEREUM_SEPOLIA_CHAIN_ID, 1_000_002);
+        let targets = [configured.clone(), configured_sepolia.clone()];
+        assert_eq!(
+            check_target_chain_ids(LINEA_CHAIN_ID, None, &[], 1, 1, &[1_000_001]),
+            Err(TargetChainError::UnsupportedTargetChain {
+                index: 0,
+                chain_id: 1_000_001
+            })
+        );
+        assert_eq!(
+            check_target_chain_ids(LINEA_CHAIN_ID, None, &targets, 1, 1, &[1_000_001]),
+            Ok(())
+        );
+        assert_eq!(
+            check_target_chain_ids(LINEA_CHAIN_ID, None, &targets, 1, 1, &[1_000_002]),
+            Err(TargetChainError::NetworkMismatch {
+                index: 0,
+                chain_id: 1_000_002
+            })
+        );
+        assert_eq!(
+            check_target_chain_ids(LINEA_SEPOLIA_CHAIN_ID, None, &targets, 1, 1, &[1_000_002]),
+            Ok(())
+        );
+        // Configured sources still only target the host chain.
+        assert_eq!(
+            check_target_chain_ids(1_000_001, Some(&configured), &[], 1, 1, &[LINEA_CHAIN_ID]),
+            Ok(())
+        );
+    }
+
+    fn active_fork_at(chain_spec: &EthChainSpec, header: &Header) -> SpecId {
+        chain_spec
+            .active_fork(header.number, header.timestamp)
+            .unwrap()
+    }
+
+    #[test]
+    fn linea_sepolia_chain_spec_forks() {
+        let chain_spec = get_eth_chain_spec(LINEA_SEPOLIA_CHAIN_ID);
+        assert_eq!(chain_spec.chain_id, LINEA_SEPOLIA_CHAIN_ID);
+        let genesis = Header {
+            number: LINEA_SEPOLIA_LONDON_BLOCK,
+            ..Default::default()
+        };
+        assert_eq!(active_fork_at(chain_spec, &genesis), SpecId::LONDON);
+        let before_prague = Header {
+            number: 10_000_000,
+            timestamp: LINEA_SEPOLIA_PRAGUE_TIMESTAMP - 1,
+            ..Default::default()
+        };
+        assert_eq!(active_fork_at(chain_spec, &before_prague), SpecId::LONDON);
+        let at_prague = Header {
+            timestamp: LINEA_SEPOLIA_PRAGUE_TIMESTAMP,
+            ..before_prague.clone()
+        };
+        assert_eq!(active_fork_at(chain_spec, &at_prague), SpecId::PRAGUE);
+        let after_prague = Header {
+            timestamp: LINEA_SEPOLIA_PRAGUE_TIMESTAMP + 1,
+            ..before_prague
+        };
+        assert_eq!(active_fork_at(chain_spec, &after_prague), SpecId::PRAGUE);
+    }
+
+    #[test]
+    fn sepolia_chain_specs() {
+        for chain_id in [
+            ETHEREUM_SEPOLIA_CHAIN_ID,
+            OPTIMISM_SEPOLIA_CHAIN_ID,
+            BASE_SEPOLIA_CHAIN_ID,
+            SCROLL_SEPOLIA_CHAIN_ID,
+        ] {
+            assert_eq!(
+                get_eth_chain_spec(chain_id).chain_id,
+                ETHEREUM_SEPOLIA_CHAIN_ID
+            );
+        }
+    }
+
+    #[test]
+    fn l1_inclusion_policy() {
+        let market = Address::repeat_byte(1);
+        let other_market = Address::repeat_byte(2);
+        let rules = [
+            L1InclusionRule::AmountOutAbove {
+                market,
+                threshold: U256::from(100),
+            },
+            L1InclusionRule::TargetChain {
+                chain_id: ETHEREUM_CHAIN_ID,
+            },
+        ];
+        let amounts = [(U256::ZERO, U256::from(100)), (U256::ZERO, U256::from(101))];
+
+        // At the threshold, for other markets and for other targets no rule applies.
+        assert_eq!(
+            check_l1_inclusion_policy(
+                &rules,
+                false,
+                &[market, other_market],
+                &[BASE_CHAIN_ID, BASE_CHAIN_ID],
+                &amounts
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_l1_inclusion_policy(
+                &rules,
+                false,
+                &[other_market, market],
+                &[BASE_CHAIN_ID, BASE_CHAIN_ID],
+                &amounts
+            ),
+            Err(L1InclusionPolicyError::InclusionRequired {
+                index: 1,
+                rule: rules[0]
+            })
+        );
+        assert_eq!(
+            check_l1_inclusion_policy(
+                &rules,
+                false,
+                &[other_market, other_market],
+                &[BASE_CHAIN_ID, ETHEREUM_CHAIN_ID],
+                &amounts
+            ),
+            Err(L1InclusionPolicyError::InclusionRequired {
+                index: 1,
+                rule: rules[1]
+            })
+        );
+        // Validated L1 inclusion satisfies every rule.
+        assert_eq!(
+            check_l1_inclusion_policy(
+                &rules,
+                true,
+                &[market, market],
+                &[ETHEREUM_CHAIN_ID, ETHEREUM_CHAIN_ID],
+                &amounts
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn linea_l1_inclusion_rules() {
+        let amounts = [(U256::ZERO, U256::from(1))];
+        let market = [Address::repeat_byte(1)];
+        let rules = get_l1_inclusion_rules(LINEA_CHAIN_ID);
+        assert!(
+            check_l1_inclusion_policy(rules, false, &market, &[BASE_CHAIN_ID], &amounts).is_ok()
+        );
+        assert!(
+            check_l1_inclusion_policy(rules, false, &market, &[ETHEREUM_CHAIN_ID], &amounts)
+                .is_err()
+        );
+        let rules = get_l1_inclusion_rules(LINEA_SEPOLIA_CHAIN_ID);
+        assert!(check_l1_inclusion_policy(
+            rules,
+            false,
+            &market,
+            &[ETHEREUM_SEPOLIA_CHAIN_ID],
+            &amounts
+        )
+        .is_err());
+        assert!(get_l1_inclusion_rules(BASE_CHAIN_ID).is_empty());
+    }
+
+    /// Builds a hash-linked chain of headers starting at block zero.
+    fn linked_headers(length: u64, block_time: u64) -> Vec<RlpHeader<Header>> {
+        let mut headers: Vec<RlpHeader<Header>> = Vec::new();
+        for number in 0..length {
+            let parent_hash = headers
+                .last()
+                .map_or(B256::ZERO, |parent| parent.hash_slow());
+            headers.push(RlpHeader::new(Header {
+                number,
+                timestamp: number * block_time,
+                parent_hash,
+                ..Default::default()
+            }));
+        }
+        headers
+    }
+
+    #[test]
+    fn mixed_reorg_protection_policies() {
+        for (chain_id, block_time) in [
+            (OPTIMISM_CHAIN_ID, OPSTACK_BLOCK_TIME_SECONDS),
+            (BASE_CHAIN_ID, OPSTACK_BLOCK_TIME_SECONDS),
+            (LINEA_CHAIN_ID, LINEA_BLOCK_TIME_SECONDS),
+            (ETHEREUM_CHAIN_ID, ETHEREUM_BLOCK_TIME_SECONDS),
+        ] {
+            let policy = get_reorg_protection_policy(chain_id);
+            let depth = policy.min_blocks.unwrap();
+            assert_eq!(policy.min_elapsed_seconds, Some(depth * block_time));
+
+            // At the nominal block time, the depth alone satisfies the policy.
+            let headers = linked_headers(depth + 1, block_time);
+            let head_hash = headers[depth as usize].hash_slow();
+            assert_eq!(
+                check_linking_blocks(
+                    chain_id,
+                    &policy,
+                    headers[0].hash_slow(),
+                    headers[0].inner(),
+                    &headers[1..],
+                    head_hash,
+                ),
+                Ok(())
+            );
+
+            // Blocks produced faster than nominal don't satisfy the time span with the same depth.
+            let headers = linked_headers(depth + 1, block_time / 2);
+            let head_hash = headers[depth as usize].hash_slow();
+            assert_eq!(
+                check_linking_blocks(
+                    chain_id,
+                    &policy,
+                    headers[0].hash_slow(),
+                    headers[0].inner(),
+                    &headers[1..],
+                    head_hash,
+                ),
+                Err(LinkingBlocksError::InsufficientTimeSpan {
+                    elapsed: depth * (block_time / 2),
+                    min: depth * block_time,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn scroll_requires_view_call_block() {
+        let headers = linked_headers(3, 1);
+        let policy = get_reorg_protection_policy(SCROLL_CHAIN_ID);
+        let head_hash = headers[2].hash_slow();
+        assert_eq!(
+            check_linking_blocks(
+                SCROLL_CHAIN_ID,
+                &policy,
+                head_hash,
+                headers[2].inner(),
+                &[],
+                head_hash,
+            ),
+            Ok(())
+        );
+        // Only the state root of the batch's last block is authenticated, so it can't vouch for a parent.
+        assert_eq!(
+            check_linking_blocks(
+                SCROLL_CHAIN_ID,
+                &policy,
+                headers[1].hash_slow(),
+                headers[1].inner(),
+                &headers[2..],
+                head_hash,
+            ),
+            Err(LinkingBlocksError::TooManyBlocks { length: 1, max: 0 })
+        );
+    }
+
+    /// Checks `headers[1..]` as the linking blocks of `headers[0]`, ending at `current_hash`.
+    fn check_linked_headers(
+        policy: &ReorgProtectionPolicy,
+        headers: &[RlpHeader<Header>],
+        current_hash: B256,
+    ) -> Result<(), LinkingBlocksError> {
+        check_linking_blocks(
+            ETHEREUM_CHAIN_ID,
+            policy,
+            headers[0].hash_slow(),
+            headers[0].inner(),
+            &headers[1..],
+            current_hash,
+        )
+    }
+
+    /// Replaces the header at `index` with a modified copy.
+    fn modify_header(
+        headers: &mut [RlpHeader<Header>],
+        index: usize,
+        modify: impl FnOnce(&mut Header),
+    ) {
+        let mut header = headers[index].inner().clone();
+        modify(&mut header);
+        headers[index] = RlpHeader::new(header);
+    }
+
+    #[test]
+    fn linking_blocks_valid() {
+        let policy = ReorgProtectionPolicy::blocks(3).with_max_blocks(5);
+        let headers = linked_headers(5, 12);
+        assert_eq!(
+            check_linked_headers(&policy, &headers, headers[4].hash_slow()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn linking_blocks_broken_parent_hash() {
+        let policy = ReorgProtectionPolicy::blocks(3).with_max_blocks(5);
+        let mut headers = linked_headers(5, 12);
+        modify_header(&mut headers, 2, |header| {
+            header.parent_hash = B256::repeat_byte(0x11)
+        });
+        assert_eq!(
+            check_linked_headers(&policy, &headers, headers[4].hash_slow()),
+            Err(LinkingBlocksError::NotHashLinked { index: 1 })
+        );
+    }
+
+    #[test]
+    fn linking_blocks_decreasing_timestamp() {
+        let policy = ReorgProtectionPolicy::blocks(3).with_max_blocks(5);
+        let mut headers = linked_headers(5, 12);
+        modify_header(&mut headers, 3, |header| header.timestamp = 23);
+        assert_eq!(
+            check_linked_headers(&policy, &headers, headers[4].hash_slow()),
+            Err(LinkingBlocksError::NonMonotonicTimestamp {
+                index: 2,
+                parent: 24,
+                found: 23
+            })
+        );
+    }
+
+    #[test]
+    fn linking_blocks_non_sequential_number() {
+        let policy = ReorgProtectionPolicy::blocks(3).with_max_blocks(5);
+        let mut headers = linked_headers(5, 12);
+        modify_header(&mut headers, 4, |header| header.number = 5);
+        assert_eq!(
+            check_linked_headers(&policy, &headers, headers[4].hash_slow()),
+            Err(LinkingBlocksError::NonSequentialNumber {
+                index: 3,
+                expected: 4,
+                found: 5
+            })
+        );
+    }
+
+    #[test]
+    fn linking_blocks_too_many() {
+        let policy = ReorgProtectionPolicy::blocks(3).with_max_blocks(5);
+        let headers = linked_headers(7, 12);
+        assert_eq!(
+            check_linked_headers(&policy, &headers, headers[6].hash_slow()),
+            Err(LinkingBlocksError::TooManyBlocks { length: 6, max: 5 })
+        );
+    }
+
+    #[test]
+    fn linking_blocks_too_few() {
+        let policy = ReorgProtectionPolicy::blocks(3).with_max_blocks(5);
+        let headers = linked_headers(3, 12);
+        assert_eq!(
+            check_linked_headers(&policy, &headers, headers[2].hash_slow()),
+            Err(LinkingBlocksError::TooFewBlocks { length: 2, min: 3 })
+        );
+    }
+
+    #[test]
+    fn linking_blocks_head_mismatch() {
+        let policy = ReorgProtectionPolicy::blocks(3).with_max_blocks(5);
+        let headers = linked_headers(5, 12);
+        let expected = B256::repeat_byte(0xff);
+        assert_eq!(
+            check_linked_headers(&policy, &headers, expected),
+            Err(LinkingBlocksError::HeadMismatch {
+                expected,
+                found: headers[4].hash_slow()
+            })
+        );
+    }
+
+    /// Returns the Merkle branch of the leaf at `index`, ordered from the leaf up.
+    fn merkle_branch(leaves: &[B256], mut index: usize) -> Vec<B256> {
+        let mut branch = Vec::new();
+        let mut layer = leaves.to_vec();
+        while layer.len() > 1 {
+            branch.push(layer[index ^ 1]);
+            layer = layer
+                .chunks(2)
+                .map(|pair| sha256_pair(&pair[0], &pair[1]))
+                .collect();
+            index /= 2;
+        }
+        branch
+    }
+
+    /// Builds a finality proof from SSZ trees laid out like a `BeaconState` with `state_leaves`
+    /// field leaves and a Deneb `BeaconBlockBody`.
+    fn finality_proof(attested_slot: u64, state_leaves: usize) -> (B256, EthereumFinalityProof) {
+        // `execution_payload.block_hash` is field 12 of 17, `execution_payload` field 9 of 12.
+        let finalized_block_hash = B256::repeat_byte(0xee);
+        let mut payload = (0..32u8).map(B256::repeat_byte).collect::<Vec<_>>();
+        payload[12] = finalized_block_hash;
+        let mut body = (32..48u8).map(B256::repeat_byte).collect::<Vec<_>>();
+        body[9] = merkleize(&payload);
+        let mut execution_block_hash_branch = merkle_branch(&payload, 12);
+        execution_block_hash_branch.extend(merkle_branch(&body, 9));
+
+        let finalized_beacon_header = BeaconBlockHeader {
+            slot: attested_slot - 64,
+            proposer_index: 7,
+            parent_root: B256::repeat_byte(0x11),
+            state_root: B256::repeat_byte(0x12),
+            body_root: merkleize(&body),
+        };
+
+        // `finalized_checkpoint` is field 20 of the state, `root` field 1 of the checkpoint.
+        let checkpoint = [
+            ssz_uint64_leaf(finalized_beacon_header.slot / 32),
+            finalized_beacon_header.hash_tree_root(),
+        ];
+        let mut state = (0..state_leaves as u8)
+            .map(|i| B256::repeat_byte(i.wrapping_add(64)))
+            .collect::<Vec<_>>();
+        state[20] = merkleize(&checkpoint);
+        let mut finalized_root_branch = vec![checkpoint[0]];
+        finalized_root_branch.extend(merkle_branch(&state, 20));
+
+        let attested_beacon_header = BeaconBlockHeader {
+            slot: attested_slot,
+            proposer_index: 8,
+            parent_root: B256::repeat_byte(0x21),
+            state_root: merkleize(&state),
+            body_root: B256::repeat_byte(0x22),
+        };
+        let anchor_header = RlpHeader::new(Header {
+            number: 100,
+            parent_beacon_block_root: Some(attested_beacon_header.hash_tree_root()),
+            ..Default::default()
+        });
+        let validated_hash = anchor_header.hash_slow();
+        (
+            validated_hash,
+            EthereumFinalityProof {
+                anchor_header,
+                attested_beacon_header,
+                finalized_root_branch,
+                finalized_beacon_header,
+                finalized_block_hash,
+                execution_block_hash_branch,
+            },
+        )
+    }
+
+    #[test]
+    fn finality_proof_electra_switch() {
+        // Deneb states have 28 fields (32 leaves), Electra states 37 fields (64 leaves).
+        let finalized_block_hash = B256::repeat_byte(0xee);
+        let deneb_slot = ELECTRA_FORK_SLOT_ETHEREUM - 1;
+        let electra_slot = ELECTRA_FORK_SLOT_ETHEREUM;
+
+        let (hash, proof) = finality_proof(deneb_slot, 32);
+        assert_eq!(
+            check_finality_proof(ETHEREUM_CHAIN_ID, hash, &proof),
+            Ok(finalized_block_hash)
+        );
+        let (hash, proof) = finality_proof(electra_slot, 64);
+        assert_eq!(
+            check_finality_proof(ETHEREUM_CHAIN_ID, hash, &proof),
+            Ok(finalized_block_hash)
+        );
+
+        // A branch for the wrong fork's state layout is rejected on either side of the switch.
+        let (hash, proof) = finality_proof(electra_slot, 32);
+        assert_eq!(
+            check_finality_proof(ETHEREUM_CHAIN_ID, hash, &proof),
+            Err(FinalityProofError::InvalidFinalizedRootBranch {
+                gindex: FINALIZED_ROOT_GINDEX_ELECTRA
+            })
+        );
+        let (hash, proof) = finality_proof(deneb_slot, 64);
+        assert_eq!(
+            check_finality_proof(ETHEREUM_CHAIN_ID, hash, &proof),
+            Err(FinalityProofError::InvalidFinalizedRootBranch {
+                gindex: FINALIZED_ROOT_GINDEX_DENEB
+            })
+        );
+
+        // Sepolia switched to Electra earlier than mainnet.
+        let (hash, proof) = finality_proof(ELECTRA_FORK_SLOT_ETHEREUM_SEPOLIA, 64);
+        assert_eq!(
+            check_finality_proof(ETHEREUM_SEPOLIA_CHAIN_ID, hash, &proof),
+            Ok(finalized_block_hash)
+        );
+        assert_eq!(
+            check_finality_proof(ETHEREUM_CHAIN_ID, hash, &proof),
+            Err(FinalityProofError::InvalidFinalizedRootBranch {
+                gindex: FINALIZED_ROOT_GINDEX_DENEB
+            })
+        );
+    }
+
+    #[test]
+    fn finality_proof_errors() {
+        let (hash, proof) = finality_proof(ELECTRA_FORK_SLOT_ETHEREUM, 64);
+        assert_eq!(
+            check_finality_proof(LINEA_CHAIN_ID, hash, &proof),
+            Err(FinalityProofError::UnsupportedChain {
+                chain_id: LINEA_CHAIN_ID
+            })
+        );
+        assert_eq!(
+            check_finality_proof(ETHEREUM_CHAIN_ID, B256::ZERO, &proof),
+            Err(FinalityProofError::AnchorHashMismatch {
+                expected: B256::ZERO,
+                found: hash
+            })
+        );
+
+        let mut pre_cancun = proof.clone();
+        pre_cancun.anchor_header = RlpHeader::new(Header {
+            number: 100,
+            ..Default::default()
+        });
+        assert_eq!(
+            check_finality_proof(
+                ETHEREUM_CHAIN_ID,
+                pre_cancun.anchor_header.hash_slow(),
+                &pre_cancun
+            ),
+            Err(FinalityProofError::MissingParentBeaconBlockRoot)
+        );
+
+        let mut attested = proof.clone();
+        attested.attested_beacon_header.proposer_index += 1;
+        assert_eq!(
+            check_finality_proof(ETHEREUM_CHAIN_ID, hash, &attested),
+            Err(FinalityProofError::AttestedHeaderMismatch {
+                expected: proof.attested_beacon_header.hash_tree_root(),
+                found: attested.attested_beacon_header.hash_tree_root()
+            })
+        );
+
+        let mut finalized = proof.clone();
+        finalized.finalized_beacon_header.slot += 1;
+        assert_eq!(
+            check_finality_proof(ETHEREUM_CHAIN_ID, hash, &finalized),
+            Err(FinalityProofError::InvalidFinalizedRootBranch {
+                gindex: FINALIZED_ROOT_GINDEX_ELECTRA
+            })
+        );
+
+        let mut block_hash = proof.clone();
+        block_hash.finalized_block_hash = B256::repeat_byte(0xef);
+        assert_eq!(
+            check_finality_proof(ETHEREUM_CHAIN_ID, hash, &block_hash),
+            Err(FinalityProofError::InvalidExecutionBlockHashBranch)
+        );
+    }
+
+    #[test]
+    fn deneb_body_merkle_branch() {
+        // Blob sidecar of a Deneb block (slot 1409759) from a public beacon node: the KZG commitment
+        // inclusion proof checks the branch orientation against a real `BeaconBlockBody` root.
+        let body_root = b256!("e63dab4a3275db621ef3a3a34848d24049c61f6c0e93deaf6b179f0e8aee97b2");
+        let commitment = alloy_primitives::hex!(
+            "adfebfe0d08b0b6f4e95bff79e2e029406e40781cca70c13f43104b81a03bf682ecfca570370b2836cb04251ef6656ae"
+        );
+        let branch = [
+            b256!("6f375622fe38528180b8bbce850131c5c287115fc0a19693a85073289b3aa1fe"),
+            b256!("4a97acf7425809951e2dfa23af457d1591f91a4c072fb5ae07a6c38b6ac02270"),
+            b256!("cdfe025837f134df085d20c9f4f48ba7469b6fe66dfd3ffe68086e3331f2ff3c"),
+            b256!("c78009fdf07fc56a11f122370658a353aaa542ed63e44c4bc15ff4cd105ab33c"),
+            b256!("536d98837f2dd165a55d5eeae91485954472d56f246df256bf3cae19352a123c"),
+            b256!("9efde052aa15429fae05bad4d0b1d7c64da64d03d7a1854a588c2cb8430c0d30"),
+            b256!("d88ddfeed400a8755596b21942c1497e114c302e6118290f91e6772976041fa1"),
+            b256!("87eb0ddba57e35f6d286673802a4af5975e22506c7cf4c64bb6be5ee11527f2c"),
+            b256!("26846476fd5fc54a5d43385167c95144f2643f533cc85bb9d16b782f8d7db193"),
+            b256!("506d86582d252405b840018792cad2bf1259f1ef5aa5f887e13cb2f0094f51e1"),
+            b256!("ffff0ad7e659772f9534c195c815efc4014ef1e1daed4404c06385d11192e92b"),
+            b256!("6cf04127db05441cd833107a52be852868890e4317e6a02ab47683aa75964220"),
+            b256!("0600000000000000000000000000000000000000000000000000000000000000"),
+            b256!("792930bbd5baac43bcc798ee49aa8185ef76bb3b44ba62b91d86ae569e4bb535"),
+            b256!("527b1cda425c4bf8128c2ebcd1c9d9f4d507237067a59ce5815079553b36c6f7"),
+            b256!("db56114e00fdd4c1f85c892bf35ac9a89289aaecb1ebd0a96cde606a748b5d71"),
+            b256!("e01c0837cb2d1b2dcb110929f0f3922b07d6712ae1e7ba65fda1eed7d79de4af"),
+        ];
+        let mut chunks = [B256::ZERO; 2];
+        chunks[0].copy_from_slice(&commitment[..32]);
+        chunks[1][..16].copy_from_slice(&commitment[32..]);
+        let leaf = sha256_pair(&chunks[0], &chunks[1]);
+
+        // `blob_kzg_commitments` is field 11 of the body, a list of up to 4096 commitments.
+        let gindex = (16 + 11) * 2 * 4096;
+        assert!(is_valid_merkle_branch(leaf, &branch, gindex, body_root));
+        assert!(!is_valid_merkle_branch(
+            leaf,
+            &branch,
+            gindex + 1,
+            body_root
+        ));
+        assert!(!is_valid_merkle_branch(
+            leaf,
+            &branch[..16],
+            gindex / 2,
+            body_root
+        ));
+    }
 }
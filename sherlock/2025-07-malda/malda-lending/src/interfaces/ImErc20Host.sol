@@ -35,6 +35,16 @@ interface ImErc20Host {
      */
     event mErc20Host_ChainStatusUpdated(uint32 indexed chainId, bool status);
 
+    /**
+     * @notice Emitted when a rollup config hash whitelist status is updated
+     */
+    event mErc20Host_ChainConfigStatusUpdated(bytes32 indexed chainConfigHash, bool status);
+
+    /**
+     * @notice Emitted when the maximum age of sequencer-signed payloads is updated
+     */
+    event mErc20Host_MaxSequencerCommitmentAgeUpdated(uint64 oldMaxAge, uint64 newMaxAge);
+
     /**
      * @notice Emitted when a liquidate operation is executed
      */
@@ -110,6 +120,16 @@ interface ImErc20Host {
      */
     error mErc20Host_ChainNotValid();
 
+    /**
+     * @notice Thrown when the rollup config hash of a proof is not whitelisted
+     */
+    error mErc20Host_ChainConfigNotValid();
+
+    /**
+     * @notice Thrown when the sequencer-signed payload of a proof is older than the maximum age
+     */
+    error mErc20Host_SequencerCommitmentStale();
+
     /**
      * @notice Thrown when the address is not valid
      */
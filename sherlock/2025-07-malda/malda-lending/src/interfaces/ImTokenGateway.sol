@@ -85,6 +85,8 @@ interface ImTokenGateway {
     event mTokenGateway_UserWhitelisted(address indexed user, bool status);
     event mTokenGateway_WhitelistEnabled();
     event mTokenGateway_WhitelistDisabled();
+    event mTokenGateway_ChainConfigStatusUpdated(bytes32 indexed chainConfigHash, bool status);
+    event mTokenGateway_MaxSequencerCommitmentAgeUpdated(uint64 maxAge);
 
     // ----------- ERRORS -----------+
     /**
@@ -160,6 +162,16 @@ interface ImTokenGateway {
      */
     error mTokenGateway_UserBlacklisted();
 
+    /**
+     * @notice Thrown when the rollup config hash of a proof is not whitelisted
+     */
+    error mTokenGateway_ChainConfigNotValid();
+
+    /**
+     * @notice Thrown when the sequencer-signed payload of a proof is older than the maximum age
+     */
+    error mTokenGateway_SequencerCommitmentStale();
+
     // ----------- VIEW -----------
     /**
      * @notice Roles
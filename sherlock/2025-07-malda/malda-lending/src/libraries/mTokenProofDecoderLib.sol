@@ -26,7 +26,7 @@ import {BytesLib} from "src/libraries/BytesLib.sol";
 */
 
 library mTokenProofDecoderLib {
-    uint256 public constant ENTRY_SIZE = 113; // 112 + 1 for L1inclusion
+    uint256 public constant ENTRY_SIZE = 153; // 112 + 1 for L1inclusion + 8 for sequencerTimestamp + 32 for chainConfigHash
 
     error mTokenProofDecoderLib_ChainNotFound();
     error mTokenProofDecoderLib_InvalidLength();
@@ -57,6 +57,8 @@ library mTokenProofDecoderLib {
         // | 104    | 4       | uint32 chainId         |
         // | 108    | 4       | uint32 dstChainId      |
         // | 112    | 1       | bool L1inclusion       |
+        // | 113    | 8       | uint64 sequencerTimestamp |
+        // | 121    | 32      | bytes32 chainConfigHash |
         sender = BytesLib.toAddress(BytesLib.slice(journalData, 0, 20), 0);
         market = BytesLib.toAddress(BytesLib.slice(journalData, 20, 20), 0);
         accAmountIn = BytesLib.toUint256(BytesLib.slice(journalData, 40, 32), 0);
@@ -69,6 +71,20 @@ library mTokenProofDecoderLib {
         L1inclusion = rawL1inclusion == 1;
     }
 
+    function decodeSequencerTimestamp(bytes memory journalData) internal pure returns (uint64 sequencerTimestamp) {
+        require(journalData.length == ENTRY_SIZE, mTokenProofDecoderLib_InvalidLength());
+
+        // zero for proofs validated with L1 inclusion
+        sequencerTimestamp = BytesLib.toUint64(BytesLib.slice(journalData, 113, 8), 0);
+    }
+
+    function decodeChainConfigHash(bytes memory journalData) internal pure returns (bytes32 chainConfigHash) {
+        require(journalData.length == ENTRY_SIZE, mTokenProofDecoderLib_InvalidLength());
+
+        // zero for built-in chains, the rollup config hash for configured chains
+        chainConfigHash = BytesLib.toBytes32(BytesLib.slice(journalData, 121, 32), 0);
+    }
+
     function encodeJournal(
         address sender,
         address market,
@@ -76,8 +92,20 @@ library mTokenProofDecoderLib {
         uint256 accAmountOut,
         uint32 chainId,
         uint32 dstChainId,
-        bool L1inclusion
+        bool L1inclusion,
+        uint64 sequencerTimestamp,
+        bytes32 chainConfigHash
     ) internal pure returns (bytes memory) {
-        return abi.encodePacked(sender, market, accAmountIn, accAmountOut, chainId, dstChainId, L1inclusion);
+        return abi.encodePacked(
+            sender,
+            market,
+            accAmountIn,
+            accAmountOut,
+            chainId,
+            dstChainId,
+            L1inclusion,
+            sequencerTimestamp,
+            chainConfigHash
+        );
     }
 }
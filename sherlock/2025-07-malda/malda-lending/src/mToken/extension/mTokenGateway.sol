@@ -71,6 +71,9 @@ contract mTokenGateway is OwnableUpgradeable, ImTokenGateway, ImTokenOperationTy
     ///@dev gas fee for `supplyOnHost`
     uint256 public gasFee;
 
+    mapping(bytes32 => bool) public allowedChainConfigs;
+    uint64 public maxSequencerCommitmentAge;
+
     /// @custom:oz-upgrades-unsafe-allow constructor
     constructor() {
         _disableInitializers();
@@ -137,6 +140,29 @@ contract mTokenGateway is OwnableUpgradeable, ImTokenGateway, ImTokenOperationTy
         emit mTokenGateway_UserWhitelisted(user, state);
     }
 
+    /**
+     * @notice Updates an allowed rollup config status
+     * @dev Proofs for chains onboarded through a rollup config commit its hash; the prover supplies the config,
+     *      so only hashes of reviewed configs may be accepted
+     * @param chainConfigHash The rollup config hash
+     * @param state The new state
+     */
+    function setAllowedChainConfig(bytes32 chainConfigHash, bool state) external onlyOwner {
+        allowedChainConfigs[chainConfigHash] = state;
+        emit mTokenGateway_ChainConfigStatusUpdated(chainConfigHash, state);
+    }
+
+    /**
+     * @notice Sets the maximum age of sequencer-signed payloads
+     * @dev Proofs without L1 inclusion commit the timestamp of the sequencer-signed payload they were validated
+     *      with; older payloads are rejected
+     * @param maxAge The maximum age in seconds
+     */
+    function setMaxSequencerCommitmentAge(uint64 maxAge) external onlyOwner {
+        maxSequencerCommitmentAge = maxAge;
+        emit mTokenGateway_MaxSequencerCommitmentAgeUpdated(maxAge);
+    }
+
     /**
      * @notice Enable user whitelist
      */
@@ -298,6 +324,8 @@ contract mTokenGateway is OwnableUpgradeable, ImTokenGateway, ImTokenOperationTy
         require(_market == address(this), mTokenGateway_AddressNotValid());
         require(_chainId == LINEA_CHAIN_ID, mTokenGateway_ChainNotValid()); // allow only Host
         require(_dstChainId == uint32(block.chainid), mTokenGateway_ChainNotValid());
+        _checkChainConfig(journalData);
+        _checkSequencerTimestamp(journalData);
         require(amount > 0, mTokenGateway_AmountNotValid());
         //~ qa: 提现额amount小于_accAmountOut 主链生成的用户可提取金额上限证明不就行了吗，为什么还要减去accAmountOut[_sender] 
         //~ a 如果仅验证 _accAmountOut >= amount ，用户可能会重复使用同一个 journalData 多次提现，导致超额提取
@@ -349,6 +377,25 @@ contract mTokenGateway is OwnableUpgradeable, ImTokenGateway, ImTokenOperationTy
         verifier.verifyInput(journalData, seal);
     }
 
+    function _checkChainConfig(bytes memory journalData) private view {
+        bytes32 chainConfigHash = mTokenProofDecoderLib.decodeChainConfigHash(journalData);
+        // built-in chains commit a zero hash
+        if (chainConfigHash != bytes32(0)) {
+            require(allowedChainConfigs[chainConfigHash], mTokenGateway_ChainConfigNotValid());
+        }
+    }
+
+    function _checkSequencerTimestamp(bytes memory journalData) private view {
+        uint64 sequencerTimestamp = mTokenProofDecoderLib.decodeSequencerTimestamp(journalData);
+        // zero for proofs that were not validated through a sequencer commitment
+        if (sequencerTimestamp != 0) {
+            require(
+                block.timestamp <= uint256(sequencerTimestamp) + maxSequencerCommitmentAge,
+                mTokenGateway_SequencerCommitmentStale()
+            );
+        }
+    }
+
     function _checkSender(address msgSender, address srcSender) private view {
         if (msgSender != srcSender) {
             require(
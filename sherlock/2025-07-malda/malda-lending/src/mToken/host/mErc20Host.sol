@@ -68,6 +68,8 @@ contract mErc20Host is mErc20Upgradable, ImErc20Host, ImTokenOperationTypes {
     mapping(uint32 => bool) public allowedChains;
     IZkVerifier public verifier;
     IGasFeesHelper public gasHelper;
+    mapping(bytes32 => bool) public allowedChainConfigs;
+    uint64 public maxSequencerCommitmentAge;
 
     /**
      * @notice Initializes the new money market
@@ -134,6 +136,31 @@ contract mErc20Host is mErc20Upgradable, ImErc20Host, ImTokenOperationTypes {
         emit mErc20Host_ChainStatusUpdated(_chainId, _status);
     }
 
+    /**
+     * @notice Updates an allowed rollup config status
+     * @dev Proofs for chains onboarded through a rollup config commit its hash; the prover supplies the config,
+     *      so only hashes of reviewed configs may be accepted
+     * @param _chainConfigHash the rollup config hash
+     * @param _status the new status
+     */
+    function updateAllowedChainConfig(bytes32 _chainConfigHash, bool _status) external {
+        _onlyAdminOrRole(_getChainsManagerRole());
+
+        allowedChainConfigs[_chainConfigHash] = _status;
+        emit mErc20Host_ChainConfigStatusUpdated(_chainConfigHash, _status);
+    }
+
+    /**
+     * @notice Updates the maximum age of sequencer-signed payloads
+     * @dev Proofs without L1 inclusion commit the timestamp of the sequencer-signed payload they were validated
+     *      with; older payloads are rejected
+     * @param _maxAge the maximum age in seconds
+     */
+    function setMaxSequencerCommitmentAge(uint64 _maxAge) external onlyAdmin {
+        emit mErc20Host_MaxSequencerCommitmentAgeUpdated(maxSequencerCommitmentAge, _maxAge);
+        maxSequencerCommitmentAge = _maxAge;
+    }
+
     /**
      * @inheritdoc ImErc20Host
      */
@@ -340,6 +367,25 @@ contract mErc20Host is mErc20Upgradable, ImErc20Host, ImTokenOperationTypes {
         require(allowedChains[chainId], mErc20Host_ChainNotValid());
     }
 
+    function _checkChainConfig(bytes memory singleJournal) internal view {
+        bytes32 chainConfigHash = mTokenProofDecoderLib.decodeChainConfigHash(singleJournal);
+        // built-in chains commit a zero hash
+        if (chainConfigHash != bytes32(0)) {
+            require(allowedChainConfigs[chainConfigHash], mErc20Host_ChainConfigNotValid());
+        }
+    }
+
+    function _checkSequencerTimestamp(bytes memory singleJournal) internal view {
+        uint64 sequencerTimestamp = mTokenProofDecoderLib.decodeSequencerTimestamp(singleJournal);
+        // zero for proofs that were not validated through a sequencer commitment
+        if (sequencerTimestamp != 0) {
+            require(
+                block.timestamp <= uint256(sequencerTimestamp) + maxSequencerCommitmentAge,
+                mErc20Host_SequencerCommitmentStale()
+            );
+        }
+    }
+
     function _checkSender(address msgSender, address srcSender) internal view {
         if (msgSender != srcSender) {
             require(
@@ -414,6 +460,8 @@ contract mErc20Host is mErc20Upgradable, ImErc20Host, ImTokenOperationTypes {
 
         // base checks
         _checkProofCall(_dstChainId, _chainId, _market, _sender);
+        _checkChainConfig(singleJournal);
+        _checkSequencerTimestamp(singleJournal);
 
         // operation checks
         {
@@ -444,6 +492,8 @@ contract mErc20Host is mErc20Upgradable, ImErc20Host, ImTokenOperationTypes {
         // base checks
         //~ check the call associated with the market
         _checkProofCall(_dstChainId, _chainId, _market, _sender);
+        _checkChainConfig(singleJournal);
+        _checkSequencerTimestamp(singleJournal);
 
         //~ mintAmount: The amount of the underlying asset to supply
         // operation checks
@@ -468,6 +518,8 @@ contract mErc20Host is mErc20Upgradable, ImErc20Host, ImTokenOperationTypes {
 
         // base checks
         _checkProofCall(_dstChainId, _chainId, _market, _sender);
+        _checkChainConfig(singleJournal);
+        _checkSequencerTimestamp(singleJournal);
 
         uint256 actualRepayAmount = _repayBehalf(receiver, repayAmount, false);
         /* 
@@ -183,6 +183,56 @@ contract mErc20Host_mint is mToken_Unit_Shared {
         assertEq(totalSupplyAfter - amount, totalSupplyBefore);
     }
 
+    function test_RevertGiven_ChainConfigIsNotWhitelisted(uint256 amount)
+        external
+        inRange(amount, SMALL, LARGE)
+        whenMintExternalIsCalled
+        givenDecodedAmountIsValid
+        whenMarketIsListed(address(mWethHost))
+    {
+        uint256[] memory amounts = new uint256[](1);
+        amounts[0] = amount;
+
+        bytes32 chainConfigHash = keccak256("rollup config");
+        bytes memory journalData = _createAccumulatedAmountJournal(
+            address(this), address(mWethHost), amount, true, 0, chainConfigHash
+        );
+
+        vm.expectRevert(ImErc20Host.mErc20Host_ChainConfigNotValid.selector);
+        mWethHost.mintExternal(journalData, "0x123", amounts, amounts, address(this));
+
+        mWethHost.updateAllowedChainConfig(chainConfigHash, true);
+        mWethHost.mintExternal(journalData, "0x123", amounts, amounts, address(this));
+        (uint256 accAmountIn,) = mWethHost.getProofData(address(this), uint32(block.chainid));
+        assertEq(accAmountIn, amount);
+    }
+
+    function test_RevertGiven_SequencerCommitmentIsStale(uint256 amount)
+        external
+        inRange(amount, SMALL, LARGE)
+        whenMintExternalIsCalled
+        givenDecodedAmountIsValid
+        whenMarketIsListed(address(mWethHost))
+    {
+        uint256[] memory amounts = new uint256[](1);
+        amounts[0] = amount;
+
+        uint64 sequencerTimestamp = uint64(block.timestamp);
+        bytes memory journalData = _createAccumulatedAmountJournal(
+            address(this), address(mWethHost), amount * 2, false, sequencerTimestamp, bytes32(0)
+        );
+
+        // proofs without L1 inclusion are only accepted from proof forwarders
+        roles.allowFor(address(this), roles.PROOF_FORWARDER(), true);
+        mWethHost.setMaxSequencerCommitmentAge(10 minutes);
+        vm.warp(block.timestamp + 10 minutes);
+        mWethHost.mintExternal(journalData, "0x123", amounts, amounts, address(this));
+
+        vm.warp(block.timestamp + 1);
+        vm.expectRevert(ImErc20Host.mErc20Host_SequencerCommitmentStale.selector);
+        mWethHost.mintExternal(journalData, "0x123", amounts, amounts, address(this));
+    }
+
     function test_SetReserveFactor(uint256 amount)
         external
         inRange(amount, SMALL, LARGE)
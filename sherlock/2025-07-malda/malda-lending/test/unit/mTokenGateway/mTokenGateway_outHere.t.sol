@@ -76,6 +76,54 @@ contract mTokenGateway_outHere is mToken_Unit_Shared {
         mWethExtension.outHere(journalData, "0x123", amounts, address(this));
     }
 
+    function test_RevertWhen_ChainConfigIsNotWhitelisted(uint256 amount)
+        external
+        inRange(amount, SMALL, LARGE)
+        givenMarketIsNotPaused
+    {
+        bytes32 chainConfigHash = keccak256("rollup config");
+        bytes memory journalData = _createAccumulatedAmountJournal(
+            address(this), address(mWethExtension), amount, true, 0, chainConfigHash
+        );
+
+        _getTokens(weth, address(mWethExtension), amount);
+
+        uint256[] memory amounts = new uint256[](1);
+        amounts[0] = amount;
+        vm.expectRevert(ImTokenGateway.mTokenGateway_ChainConfigNotValid.selector);
+        mWethExtension.outHere(journalData, "0x123", amounts, address(this));
+
+        mWethExtension.setAllowedChainConfig(chainConfigHash, true);
+        mWethExtension.outHere(journalData, "0x123", amounts, address(this));
+        assertEq(mWethExtension.accAmountOut(address(this)), amount);
+    }
+
+    function test_RevertWhen_SequencerCommitmentIsStale(uint256 amount)
+        external
+        inRange(amount, SMALL, LARGE)
+        givenMarketIsNotPaused
+    {
+        uint64 sequencerTimestamp = uint64(block.timestamp);
+        bytes memory journalData = _createAccumulatedAmountJournal(
+            address(this), address(mWethExtension), amount, false, sequencerTimestamp, bytes32(0)
+        );
+
+        _getTokens(weth, address(mWethExtension), amount);
+
+        uint256[] memory amounts = new uint256[](1);
+        amounts[0] = amount;
+        // proofs without L1 inclusion are only accepted from proof forwarders
+        roles.allowFor(address(this), roles.PROOF_FORWARDER(), true);
+        mWethExtension.setMaxSequencerCommitmentAge(10 minutes);
+        vm.warp(block.timestamp + 10 minutes + 1);
+        vm.expectRevert(ImTokenGateway.mTokenGateway_SequencerCommitmentStale.selector);
+        mWethExtension.outHere(journalData, "0x123", amounts, address(this));
+
+        mWethExtension.setMaxSequencerCommitmentAge(20 minutes);
+        mWethExtension.outHere(journalData, "0x123", amounts, address(this));
+        assertEq(mWethExtension.accAmountOut(address(this)), amount);
+    }
+
     function test_WhenParametersAreRight(uint256 amount)
         external
         inRange(amount, SMALL, LARGE)
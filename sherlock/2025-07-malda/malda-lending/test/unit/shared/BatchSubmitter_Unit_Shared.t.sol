@@ -134,8 +134,17 @@ abstract contract BatchSubmitter_Unit_Shared is Base_Unit_Test {
         bytes[] memory journals = new bytes[](senders.length);
 
         for (uint256 i = 0; i < senders.length;) {
-            journals[i] =
-                abi.encodePacked(senders[i], markets[i], amounts[i], amounts[i], srcChainId, dstChainId, L1inclusion);
+            journals[i] = abi.encodePacked(
+                senders[i],
+                markets[i],
+                amounts[i],
+                amounts[i],
+                srcChainId,
+                dstChainId,
+                L1inclusion,
+                uint64(0),
+                bytes32(0)
+            );
 
             unchecked {
                 ++i;
@@ -120,6 +120,17 @@ abstract contract mToken_Unit_Shared is Base_Unit_Test {
         view
         returns (bytes memory)
     {
+        return _createAccumulatedAmountJournal(sender, market, accAmount, true, 0, bytes32(0));
+    }
+
+    function _createAccumulatedAmountJournal(
+        address sender,
+        address market,
+        uint256 accAmount,
+        bool L1inclusion,
+        uint64 sequencerTimestamp,
+        bytes32 chainConfigHash
+    ) internal view returns (bytes memory) {
         // decode action data
         // | Offset | Length | Data Type               |
         // |--------|---------|----------------------- |
@@ -130,8 +141,19 @@ abstract contract mToken_Unit_Shared is Base_Unit_Test {
         // | 104    | 4       | uint32 chainId         |
         // | 108    | 4       | uint32 dstChainId      |
         // | 112    | 1       | bool L1inclusion       |
-        bytes memory journal =
-            abi.encodePacked(sender, market, accAmount, accAmount, uint32(block.chainid), uint32(block.chainid), true);
+        // | 113    | 8       | uint64 sequencerTimestamp |
+        // | 121    | 32      | bytes32 chainConfigHash |
+        bytes memory journal = abi.encodePacked(
+            sender,
+            market,
+            accAmount,
+            accAmount,
+            uint32(block.chainid),
+            uint32(block.chainid),
+            L1inclusion,
+            sequencerTimestamp,
+            chainConfigHash
+        );
         bytes[] memory journals = new bytes[](1);
         journals[0] = journal;
         return abi.encode(journals);